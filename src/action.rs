@@ -1,11 +1,22 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 use serde::{
     de::{self, Deserializer, Visitor},
     Deserialize, Serialize,
 };
 
-use crate::tab::Tab;
+use crate::{
+    cache::{PlatformState, Snapshot},
+    diff::DeploymentDiff,
+    layout::{LayoutCommand, Orientation, Side},
+    manifest::Workspace,
+    probe::Probe,
+    request::{HttpRequest, HttpResponse},
+    shuttle::models::{DeploymentDetails, LogItem, Project},
+    sql::QueryResult,
+    tab::Tab,
+    task::TaskId,
+};
 
 //// ANCHOR: action_enum
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -28,9 +39,119 @@ pub enum Action {
     Update,
     NextTab,
     PreviousTab,
+    ToggleShowTasks,
+    CancelTask(TaskId),
+    TaskCompleted(TaskId),
+    TaskCancelled(TaskId),
+    TaskFailed(TaskId, String),
+    ConfirmQuit,
+    ForceQuit,
+    PlatformFetched(String, Box<PlatformState>),
+    PlatformUnreachable(String, String),
+    PlatformUpdated(Box<Snapshot>),
+    SelectProject(String),
+    /// Opens the wizard that creates a project.
+    NewProject,
+    /// Opens the flow that writes a new project from a template.
+    InitProject,
+    /// Opens the graph of the deployments and resources of a project.
+    OpenGraph(String),
+    CloseGraph,
+    /// Opens the custom domains of a project.
+    OpenDomains(String),
+    CloseDomains,
+    AddDomain(String, String),
+    DeleteDomain(String, String),
+    /// Manifests of the Cargo project in the current directory.
+    WorkspaceDetected(Box<Workspace>),
+    /// Creates a project with the given idle minutes.
+    CreateProject(String, u64),
+    ProjectProvisioning(Project),
+    ProjectCreationFailed(String, String),
+    /// Opens the SQL console on the database of a project, or on a
+    /// `postgres://` or `sqlite://` URL.
+    OpenSqlConsole(String),
+    CloseSqlConsole,
+    /// Database that the SQL console is connected to.
+    SqlConnected(String),
+    RunQuery(String),
+    QueryFinished(Box<QueryResult>),
+    QueryFailed(String),
+    ServiceProbed(Box<Probe>),
+    /// Opens the requests to the service of a project.
+    OpenRequests(String),
+    CloseRequests,
+    /// Sends a request to the service of a project.
+    SendRequest(String, Box<HttpRequest>),
+    /// Response to the request with the given name.
+    RequestCompleted(String, Box<HttpResponse>),
+    RequestFailed(String, String),
+    ToggleShowProfiles,
+    SwitchProfile(String),
+    ProfileChanged(String),
+    OpenDeployment(String, String),
+    CloseDeployment,
+    DeploymentDetailsFetched(Box<DeploymentDetails>),
+    CompareDeployments(String, String, String),
+    CloseComparison,
+    DeploymentsCompared(Box<DeploymentDiff>),
+    Redeploy(String, String),
+    OpenLogs(String, String),
+    CloseLogs,
+    LogsFetched(String, String, Vec<LogItem>),
+    StartLocal,
+    RestartLocal,
+    StopLocal,
+    LocalStarted(Option<u32>),
+    LocalOutput(String),
+    LocalExited(Option<i32>),
+    LocalStopped,
+    LocalFilesChanged,
+    ScriptsChanged,
+    ToggleTerminal,
+    TerminalExited,
+    Layout(LayoutCommand),
+    /// Unbinds a key that is bound in an outer scope.
+    Nop,
+    FocusNext,
+    FocusPrevious,
+    GoToTab(Tab),
+    /// Sent by the application after it switched to another tab.
+    TabChanged(Tab),
+    /// Runs a command line in the shell in the background.
+    RunCommand(String),
+    /// Runs a command of the `commands` section of the configuration.
+    Command(String),
+    /// Shows the name and output of a finished command in a popup.
+    ShowOutput(String, String),
+    CloseOutput,
+    /// Shows a message in the notification area.
+    Notify(String),
 }
 //// ANCHOR_END: action_enum
 
+impl Action {
+    /// Returns `true` if the action should be delivered to the components of
+    /// every tab, not only the active one.
+    pub fn is_broadcast(&self) -> bool {
+        matches!(
+            self,
+            Action::PlatformUpdated(_)
+                | Action::SelectProject(_)
+                | Action::WorkspaceDetected(_)
+                | Action::DeploymentDetailsFetched(_)
+                | Action::DeploymentsCompared(_)
+                | Action::OpenLogs(..)
+                | Action::CloseLogs
+                | Action::LogsFetched(..)
+                | Action::LocalStarted(_)
+                | Action::LocalOutput(_)
+                | Action::LocalExited(_)
+                | Action::LocalStopped
+        )
+    }
+}
+
 impl<'de> Deserialize<'de> for Action {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -49,42 +170,298 @@ impl<'de> Deserialize<'de> for Action {
             where
                 E: de::Error,
             {
-                match value {
-                    "Tick" => Ok(Action::Tick),
-                    "Render" => Ok(Action::Render),
-                    "Suspend" => Ok(Action::Suspend),
-                    "Resume" => Ok(Action::Resume),
-                    "Quit" => Ok(Action::Quit),
-                    "Refresh" => Ok(Action::Refresh),
-                    "Help" => Ok(Action::Help),
-                    "ToggleShowHelp" => Ok(Action::ToggleShowHelp),
-                    "EnterInsert" => Ok(Action::EnterInsert),
-                    "EnterNormal" => Ok(Action::EnterNormal),
-                    "NextTab" => Ok(Action::NextTab),
-                    "PreviousTab" => Ok(Action::PreviousTab),
-                    data if data.starts_with("Error(") => {
-                        let error_msg = data.trim_start_matches("Error(").trim_end_matches(")");
-                        Ok(Action::Error(error_msg.to_string()))
-                    }
-                    data if data.starts_with("Resize(") => {
-                        let parts: Vec<&str> = data
-                            .trim_start_matches("Resize(")
-                            .trim_end_matches(")")
-                            .split(',')
-                            .collect();
-                        if parts.len() == 2 {
-                            let width: u16 = parts[0].trim().parse().map_err(E::custom)?;
-                            let height: u16 = parts[1].trim().parse().map_err(E::custom)?;
-                            Ok(Action::Resize(width, height))
-                        } else {
-                            Err(E::custom(format!("Invalid Resize format: {}", value)))
+                value
+                    .parse()
+                    .map_err(|e| E::custom(format!("Invalid action `{value}`: {e}")))
+            }
+        }
+
+        deserializer.deserialize_str(ActionVisitor)
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    /// Parses an action such as `Quit`, `GoToTab(Projects)` or
+    /// `RunCommand("echo a, b")`.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        if raw.trim() == "<Nop>" {
+            return Ok(Action::Nop);
+        }
+        let (name, args) = parse_call(raw)?;
+        let mut args = Arguments {
+            name,
+            values: args,
+            position: 0,
+        };
+        let action = match name {
+            "Tick" => Action::Tick,
+            "Render" => Action::Render,
+            "Suspend" => Action::Suspend,
+            "Resume" => Action::Resume,
+            "Quit" => Action::Quit,
+            "Refresh" => Action::Refresh,
+            "Help" => Action::Help,
+            "ToggleShowHelp" => Action::ToggleShowHelp,
+            "EnterInsert" => Action::EnterInsert,
+            "EnterNormal" => Action::EnterNormal,
+            "NextTab" => Action::NextTab,
+            "PreviousTab" => Action::PreviousTab,
+            "ToggleShowTasks" => Action::ToggleShowTasks,
+            "ForceQuit" => Action::ForceQuit,
+            "ToggleShowProfiles" => Action::ToggleShowProfiles,
+            "StartLocal" => Action::StartLocal,
+            "RestartLocal" => Action::RestartLocal,
+            "StopLocal" => Action::StopLocal,
+            "ToggleTerminal" => Action::ToggleTerminal,
+            "Nop" => Action::Nop,
+            "CloseLogs" => Action::CloseLogs,
+            "NewProject" => Action::NewProject,
+            "InitProject" => Action::InitProject,
+            "CloseGraph" => Action::CloseGraph,
+            "CloseDomains" => Action::CloseDomains,
+            "CloseSqlConsole" => Action::CloseSqlConsole,
+            "CloseRequests" => Action::CloseRequests,
+            "FocusNext" => Action::FocusNext,
+            "FocusPrevious" => Action::FocusPrevious,
+            "FocusLeft" => Action::Layout(LayoutCommand::Focus(Side::Left)),
+            "FocusDown" => Action::Layout(LayoutCommand::Focus(Side::Down)),
+            "FocusUp" => Action::Layout(LayoutCommand::Focus(Side::Up)),
+            "FocusRight" => Action::Layout(LayoutCommand::Focus(Side::Right)),
+            "SplitRight" => Action::Layout(LayoutCommand::Split(Orientation::Horizontal)),
+            "SplitBelow" => Action::Layout(LayoutCommand::Split(Orientation::Vertical)),
+            "ClosePane" => Action::Layout(LayoutCommand::Close),
+            "CyclePane" => Action::Layout(LayoutCommand::Cycle),
+            "GrowPane" => Action::Layout(LayoutCommand::Grow),
+            "ShrinkPane" => Action::Layout(LayoutCommand::Shrink),
+            "ZoomPane" => Action::Layout(LayoutCommand::ToggleZoom),
+            "EqualizePanes" => Action::Layout(LayoutCommand::Equalize),
+            "Error" => Action::Error(args.next()?),
+            "CancelTask" => Action::CancelTask(args.parse("task ID")?),
+            "SwitchProfile" => Action::SwitchProfile(args.next()?),
+            "Resize" => Action::Resize(args.parse("width")?, args.parse("height")?),
+            "GoToTab" => Action::GoToTab(args.parse("tab")?),
+            "RunCommand" => Action::RunCommand(args.next()?),
+            "Command" => Action::Command(args.next()?),
+            "Notify" => Action::Notify(args.next()?),
+            "SelectProject" => Action::SelectProject(args.next()?),
+            "OpenLogs" => Action::OpenLogs(args.next()?, args.next()?),
+            "OpenDomains" => Action::OpenDomains(args.next()?),
+            "OpenSqlConsole" => Action::OpenSqlConsole(args.next()?),
+            "RunQuery" => Action::RunQuery(args.next()?),
+            "OpenRequests" => Action::OpenRequests(args.next()?),
+            _ => return Err(format!("unknown action `{name}`")),
+        };
+        args.finish()?;
+        Ok(action)
+    }
+}
+
+/// Arguments of an action that are consumed in order.
+struct Arguments<'a> {
+    name: &'a str,
+    values: Vec<String>,
+    position: usize,
+}
+
+impl Arguments<'_> {
+    fn next(&mut self) -> Result<String, String> {
+        let value =
+            self.values.get(self.position).cloned().ok_or_else(|| {
+                format!("`{}` is missing argument {}", self.name, self.position + 1)
+            })?;
+        self.position += 1;
+        Ok(value)
+    }
+
+    fn parse<T>(&mut self, what: &str) -> Result<T, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.next()?;
+        value
+            .parse()
+            .map_err(|e| format!("invalid {what} `{value}` ({e})"))
+    }
+
+    /// Fails if there are arguments left.
+    fn finish(&self) -> Result<(), String> {
+        match (self.position, self.values.len()) {
+            (expected, given) if expected == given => Ok(()),
+            (0, _) => Err(format!("`{}` does not take arguments", self.name)),
+            (expected, given) => Err(format!(
+                "`{}` takes {expected} argument(s) but {given} were given",
+                self.name
+            )),
+        }
+    }
+}
+
+/// Splits an action into its name and arguments.
+///
+/// Arguments are separated by commas. They can be put in double or single
+/// quotes to keep commas, parentheses and surrounding spaces, in which case
+/// a backslash escapes the next character.
+fn parse_call(raw: &str) -> Result<(&str, Vec<String>), String> {
+    let raw = raw.trim();
+    let end = raw
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(raw.len());
+    let (name, rest) = raw.split_at(end);
+    if name.is_empty() {
+        return Err(String::from("expected an action name"));
+    }
+    let rest = rest.trim_start();
+    if rest.is_empty() {
+        return Ok((name, Vec::new()));
+    }
+    let Some(rest) = rest.strip_prefix('(') else {
+        return Err(format!("expected `(` after `{name}`, found `{rest}`"));
+    };
+    let mut args = Vec::new();
+    let mut chars = rest.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.peek() {
+            None => return Err(String::from("missing `)`")),
+            Some(')') if args.is_empty() => {
+                chars.next();
+                break;
+            }
+            Some(&quote @ ('"' | '\'')) => {
+                chars.next();
+                let mut arg = String::new();
+                loop {
+                    match chars.next() {
+                        None => {
+                            return Err(format!(
+                                "unterminated quote in argument {}",
+                                args.len() + 1
+                            ))
                         }
+                        Some('\\') => match chars.next() {
+                            Some(c) => arg.push(c),
+                            None => return Err(String::from("unterminated escape")),
+                        },
+                        Some(c) if c == quote => break,
+                        Some(c) => arg.push(c),
                     }
-                    _ => Err(E::custom(format!("Unknown Action variant: {}", value))),
                 }
+                args.push(arg);
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
             }
+            Some(_) => {
+                let mut arg = String::new();
+                let mut depth = 0;
+                while let Some(&c) = chars.peek() {
+                    match c {
+                        ',' | ')' if depth == 0 => break,
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                    arg.push(c);
+                    chars.next();
+                }
+                let arg = arg.trim();
+                if arg.is_empty() {
+                    return Err(format!("argument {} is empty", args.len() + 1));
+                }
+                args.push(arg.to_string());
+            }
+        }
+        match chars.next() {
+            Some(',') => {}
+            Some(')') => break,
+            Some(c) => {
+                return Err(format!(
+                    "expected `,` or `)` after argument {}, found `{c}`",
+                    args.len()
+                ))
+            }
+            None => return Err(String::from("missing `)`")),
         }
+    }
+    let rest = chars.collect::<String>();
+    if !rest.trim().is_empty() {
+        return Err(format!("unexpected `{}` after `)`", rest.trim()));
+    }
+    Ok((name, args))
+}
 
-        deserializer.deserialize_str(ActionVisitor)
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_action() {
+        let parse = |raw: &str| raw.parse::<Action>();
+        assert_eq!(parse(" Quit "), Ok(Action::Quit));
+        assert_eq!(parse("Quit()"), Ok(Action::Quit));
+        assert_eq!(parse("<Nop>"), Ok(Action::Nop));
+        assert_eq!(
+            parse("GoToTab(Projects)"),
+            Ok(Action::GoToTab(Tab::Projects))
+        );
+        assert_eq!(
+            parse("RunCommand(cargo shuttle deploy)"),
+            Ok(Action::RunCommand(String::from("cargo shuttle deploy")))
+        );
+        assert_eq!(
+            parse(r#"RunCommand("echo \"a, (b)\"")"#),
+            Ok(Action::RunCommand(String::from(r#"echo "a, (b)""#)))
+        );
+        assert_eq!(
+            parse("RunCommand(sh -c 'ls (x)')"),
+            Ok(Action::RunCommand(String::from("sh -c 'ls (x)'")))
+        );
+        assert_eq!(
+            parse("SwitchProfile( ' work ' )"),
+            Ok(Action::SwitchProfile(String::from(" work ")))
+        );
+        assert_eq!(parse("Resize(80, 24)"), Ok(Action::Resize(80, 24)));
+        assert_eq!(parse("CancelTask(3)"), Ok(Action::CancelTask(3)));
+        assert_eq!(
+            parse("OpenLogs(api, d1)"),
+            Ok(Action::OpenLogs(String::from("api"), String::from("d1")))
+        );
+    }
+
+    #[test]
+    fn test_parse_action_errors() {
+        let parse = |raw: &str| raw.parse::<Action>().unwrap_err();
+        assert_eq!(parse(""), "expected an action name");
+        assert_eq!(parse("Launch"), "unknown action `Launch`");
+        assert_eq!(parse("Quit(now)"), "`Quit` does not take arguments");
+        assert_eq!(parse("Quit now"), "expected `(` after `Quit`, found `now`");
+        assert_eq!(parse("GoToTab("), "missing `)`");
+        assert_eq!(parse("GoToTab(Projects"), "missing `)`");
+        assert_eq!(parse("GoToTab()"), "`GoToTab` is missing argument 1");
+        assert_eq!(
+            parse("GoToTab(Settings)"),
+            "invalid tab `Settings` (expected one of Home, Projects, Deployments, Logs, Local, Dashboard)"
+        );
+        assert_eq!(
+            parse("RunCommand(echo a, b)"),
+            "`RunCommand` takes 1 argument(s) but 2 were given"
+        );
+        assert_eq!(
+            parse("RunCommand(\"echo)"),
+            "unterminated quote in argument 1"
+        );
+        assert_eq!(parse("Resize(80,)"), "argument 2 is empty");
+        assert_eq!(
+            parse("Resize(80, tall)"),
+            "invalid height `tall` (invalid digit found in string)"
+        );
+        assert_eq!(parse("Resize(80, 24) x"), "unexpected `x` after `)`");
+        assert_eq!(
+            parse(r#"SwitchProfile("a" b)"#),
+            "expected `,` or `)` after argument 1, found `b`"
+        );
     }
 }
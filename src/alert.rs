@@ -0,0 +1,285 @@
+use std::io::Write;
+
+use crate::{
+    cache::PlatformState,
+    config::{DesktopProtocol, EventAlerts, NotificationsConfig},
+    shuttle::models::DeploymentState,
+};
+
+/// Saves the title of the terminal window on the stack of the terminal.
+pub const PUSH_TITLE: &str = "\x1b[22;0t";
+
+/// Restores the title of the terminal window that was saved last.
+pub const POP_TITLE: &str = "\x1b[23;0t";
+
+/// Kind of a deployment event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    Deployed,
+    Crashed,
+    Stopped,
+}
+
+impl EventKind {
+    fn from_state(state: &DeploymentState) -> Option<Self> {
+        match state {
+            DeploymentState::Running => Some(Self::Deployed),
+            DeploymentState::Crashed => Some(Self::Crashed),
+            DeploymentState::Stopped | DeploymentState::Completed => Some(Self::Stopped),
+            _ => None,
+        }
+    }
+}
+
+/// Deployment that reached a final state between two polls of the platform.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeploymentEvent {
+    pub kind: EventKind,
+    pub project: String,
+    pub deployment_id: String,
+}
+
+impl DeploymentEvent {
+    /// Returns a short description of the event, e.g. `api crashed`.
+    pub fn summary(&self) -> String {
+        let verb = match self.kind {
+            EventKind::Deployed => "deployed",
+            EventKind::Crashed => "crashed",
+            EventKind::Stopped => "stopped",
+        };
+        format!("{} {verb}", self.project)
+    }
+
+    fn body(&self) -> String {
+        let state = match self.kind {
+            EventKind::Deployed => "is running",
+            EventKind::Crashed => "crashed",
+            EventKind::Stopped => "stopped",
+        };
+        let id = self.deployment_id.get(..7).unwrap_or(&self.deployment_id);
+        format!("Deployment {id} of {} {state}", self.project)
+    }
+
+    /// Returns how the event is configured to alert.
+    pub fn alerts<'a>(&self, config: &'a NotificationsConfig) -> &'a EventAlerts {
+        match self.kind {
+            EventKind::Deployed => &config.deployed,
+            EventKind::Crashed => &config.crashed,
+            EventKind::Stopped => &config.stopped,
+        }
+    }
+
+    /// Returns the escape sequences of the bell and the desktop notification,
+    /// which are empty if the event is not configured to ring or notify.
+    pub fn sequence(&self, config: &NotificationsConfig) -> String {
+        let alerts = self.alerts(config);
+        let mut sequence = String::new();
+        if alerts.bell {
+            sequence.push('\x07');
+        }
+        if alerts.desktop {
+            let body = sanitize(&self.body());
+            sequence.push_str(&match config.protocol {
+                DesktopProtocol::Osc9 => format!("\x1b]9;{body}\x07"),
+                DesktopProtocol::Osc777 => format!("\x1b]777;notify;Shuttle;{body}\x07"),
+            });
+        }
+        sequence
+    }
+}
+
+/// Signals of the deployment events between two polls of the platform.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Alert {
+    /// Escape sequences of the bells and the desktop notifications.
+    pub sequence: String,
+    /// Title of the window for the last event that is configured to show it.
+    pub title: Option<String>,
+}
+
+/// Returns the signals of the deployments that reached a final state since
+/// the previous poll, which are only raised while the terminal is in the
+/// background.
+pub fn poll_alert(
+    previous: &PlatformState,
+    fresh: &PlatformState,
+    focused: bool,
+    config: &NotificationsConfig,
+) -> Alert {
+    let mut alert = Alert::default();
+    if focused {
+        return alert;
+    }
+    for event in deployment_events(previous, fresh) {
+        log::info!("Deployment event: {}", event.summary());
+        alert.sequence.push_str(&event.sequence(config));
+        if event.alerts(config).title {
+            alert.title = Some(format!("{} · Shuttle", event.summary()));
+        }
+    }
+    alert
+}
+
+/// Returns the escape sequence that sets the title of the terminal window.
+pub fn title_sequence(title: &str) -> String {
+    format!("\x1b]0;{}\x07", sanitize(title))
+}
+
+/// Returns the title of the terminal window, with the state of the selected
+/// project if there is one.
+pub fn window_title(project: Option<&str>, state: &PlatformState) -> String {
+    match project.and_then(|name| Some((name, state.project_status(name)?))) {
+        Some((name, status)) => format!("{name}: {status} · Shuttle"),
+        None => String::from("Shuttle"),
+    }
+}
+
+/// Removes the characters that would end an escape sequence early.
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control())
+        .map(|c| if c == ';' { ',' } else { c })
+        .collect()
+}
+
+/// Writes escape sequences to the terminal, which is drawn on stderr.
+pub fn write(sequence: &str) {
+    if sequence.is_empty() {
+        return;
+    }
+    let mut stderr = std::io::stderr();
+    if let Err(e) = stderr
+        .write_all(sequence.as_bytes())
+        .and_then(|_| stderr.flush())
+    {
+        log::error!("Failed to write to the terminal: {e}");
+    }
+}
+
+/// Returns the deployments that reached a final state since the previous state.
+///
+/// Projects that were not known before are skipped, so that the first poll
+/// does not report every deployment.
+pub fn deployment_events(previous: &PlatformState, fresh: &PlatformState) -> Vec<DeploymentEvent> {
+    let mut events = Vec::new();
+    for (project, deployments) in &fresh.deployments {
+        let Some(known) = previous.deployments.get(project) else {
+            continue;
+        };
+        for deployment in deployments {
+            let Some(kind) = EventKind::from_state(&deployment.state) else {
+                continue;
+            };
+            let unchanged = known
+                .iter()
+                .any(|v| v.id == deployment.id && v.state == deployment.state);
+            if !unchanged {
+                events.push(DeploymentEvent {
+                    kind,
+                    project: project.clone(),
+                    deployment_id: deployment.id.clone(),
+                });
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::shuttle::models::Deployment;
+
+    #[test]
+    fn test_deployment_events() {
+        let deployment = |id: &str, state| Deployment {
+            id: id.to_string(),
+            service_id: String::new(),
+            state,
+            last_update: Utc::now(),
+            git_commit_id: None,
+            git_commit_msg: None,
+            git_branch: None,
+            git_dirty: None,
+        };
+        let state = |project: &str, deployments| PlatformState {
+            deployments: [(project.to_string(), deployments)].into(),
+            ..Default::default()
+        };
+        let previous = state(
+            "api",
+            vec![
+                deployment("1234567890", DeploymentState::Building),
+                deployment("2", DeploymentState::Running),
+            ],
+        );
+        let fresh = state(
+            "api",
+            vec![
+                deployment("1234567890", DeploymentState::Crashed),
+                deployment("2", DeploymentState::Running),
+                deployment("3", DeploymentState::Queued),
+            ],
+        );
+        let events = deployment_events(&previous, &fresh);
+        assert_eq!(
+            events,
+            [DeploymentEvent {
+                kind: EventKind::Crashed,
+                project: String::from("api"),
+                deployment_id: String::from("1234567890"),
+            }]
+        );
+        assert!(deployment_events(&PlatformState::default(), &fresh).is_empty());
+
+        let mut config = NotificationsConfig::default();
+        assert_eq!(
+            events[0].sequence(&config),
+            "\x07\x1b]9;Deployment 1234567 of api crashed\x07"
+        );
+        config.protocol = DesktopProtocol::Osc777;
+        config.crashed.bell = false;
+        assert_eq!(
+            events[0].sequence(&config),
+            "\x1b]777;notify;Shuttle;Deployment 1234567 of api crashed\x07"
+        );
+    }
+
+    #[test]
+    fn test_poll_alert() {
+        let deployment = |state| Deployment {
+            id: String::from("1234567890"),
+            service_id: String::new(),
+            state,
+            last_update: Utc::now(),
+            git_commit_id: None,
+            git_commit_msg: None,
+            git_branch: None,
+            git_dirty: None,
+        };
+        let poll = |state| PlatformState {
+            deployments: [(String::from("api"), vec![deployment(state)])].into(),
+            ..Default::default()
+        };
+        let config = NotificationsConfig::default();
+        let previous = poll(DeploymentState::Running);
+        assert_eq!(
+            poll_alert(&previous, &poll(DeploymentState::Running), false, &config),
+            Alert::default()
+        );
+        assert_eq!(
+            poll_alert(&previous, &poll(DeploymentState::Crashed), false, &config),
+            Alert {
+                sequence: String::from("\x07\x1b]9;Deployment 1234567 of api crashed\x07"),
+                title: Some(String::from("api crashed · Shuttle")),
+            }
+        );
+        assert_eq!(
+            poll_alert(&previous, &poll(DeploymentState::Crashed), true, &config),
+            Alert::default()
+        );
+    }
+}
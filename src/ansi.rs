@@ -0,0 +1,135 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Parses a line of terminal output into styled spans.
+///
+/// Only SGR sequences (`ESC [ ... m`) are interpreted, every other escape
+/// sequence is dropped.
+pub fn parse_line(input: &str) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut style = Style::default();
+    let mut text = String::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            if c != '\r' {
+                text.push(c);
+            }
+            continue;
+        }
+        if chars.peek() != Some(&'[') {
+            // Two-character escape sequence.
+            chars.next();
+            continue;
+        }
+        chars.next();
+        let mut params = String::new();
+        let mut terminator = None;
+        for c in chars.by_ref() {
+            if ('@'..='~').contains(&c) {
+                terminator = Some(c);
+                break;
+            }
+            params.push(c);
+        }
+        if terminator != Some('m') {
+            continue;
+        }
+        if !text.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut text), style));
+        }
+        style = apply_sgr(style, &params);
+    }
+    if !text.is_empty() {
+        spans.push(Span::styled(text, style));
+    }
+    Line::from(spans)
+}
+
+/// Applies the parameters of an SGR sequence to the style.
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    let codes = params
+        .split(';')
+        .map(|v| v.parse::<u8>().unwrap_or(0))
+        .collect::<Vec<_>>();
+    let mut codes = codes.into_iter();
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            29 => style.remove_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(Color::Indexed(code - 30)),
+            38 => match extended_color(&mut codes) {
+                Some(color) => style.fg(color),
+                None => style,
+            },
+            39 => style.fg(Color::Reset),
+            40..=47 => style.bg(Color::Indexed(code - 40)),
+            48 => match extended_color(&mut codes) {
+                Some(color) => style.bg(color),
+                None => style,
+            },
+            49 => style.bg(Color::Reset),
+            90..=97 => style.fg(Color::Indexed(code - 90 + 8)),
+            100..=107 => style.bg(Color::Indexed(code - 100 + 8)),
+            _ => style,
+        };
+    }
+    style
+}
+
+/// Parses the color of a `38;5;n` or `38;2;r;g;b` sequence.
+fn extended_color(codes: &mut impl Iterator<Item = u8>) -> Option<Color> {
+    match codes.next()? {
+        5 => codes.next().map(Color::Indexed),
+        2 => Some(Color::Rgb(codes.next()?, codes.next()?, codes.next()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(parse_line("plain"), Line::from(vec![Span::raw("plain")]));
+        assert_eq!(
+            parse_line("\x1b[1;32mCompiling\x1b[0m app\r"),
+            Line::from(vec![
+                Span::styled(
+                    "Compiling",
+                    Style::default()
+                        .fg(Color::Indexed(2))
+                        .add_modifier(Modifier::BOLD)
+                ),
+                Span::raw(" app"),
+            ])
+        );
+        assert_eq!(
+            parse_line("\x1b[38;2;253;145;62mshuttle\x1b[K\x1b[48;5;236m!"),
+            Line::from(vec![
+                Span::styled("shuttle", Style::default().fg(Color::Rgb(253, 145, 62))),
+                Span::styled(
+                    "!",
+                    Style::default()
+                        .fg(Color::Rgb(253, 145, 62))
+                        .bg(Color::Indexed(236))
+                ),
+            ])
+        );
+    }
+}
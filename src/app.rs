@@ -1,30 +1,112 @@
-use color_eyre::eyre::Result;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::{bail, Result};
 use crossterm::event::KeyEvent;
 use enum_iterator::{next_cycle, previous_cycle, Sequence};
-use ratatui::prelude::Rect;
+use notify::RecommendedWatcher;
+use ratatui::{prelude::*, widgets::*};
 use serde::{Deserialize, Serialize};
-use tokio::sync::mpsc;
+use tokio::sync::mpsc::{self, UnboundedSender};
 
 use crate::{
     action::Action,
+    alert,
     args::Args,
-    components::{deployments::Deployments, home::Home, projects::Projects, tab::Tabs, Component},
-    config::Config,
-    shuttle::Shuttle,
+    cache::{Cache, PlatformState},
+    components::{
+        command_output::CommandOutput, dashboard::Dashboard,
+        deployment_details::DeploymentDetailsPage, deployment_diff::DeploymentDiffPane,
+        deployments::Deployments, domains::Domains, home::Home, init::Init, local::Local,
+        logs::LogViewer, new_project::NewProject, notifications::Notifications, profiles::Profiles,
+        projects::Projects, request_runner::RequestRunner, resource_graph::ResourceGraph,
+        sql_console::SqlConsole, tab::Tabs, tasks::Tasks, terminal::TerminalPane, Component,
+    },
+    config::{Config, OutputMode, Scope},
+    diff::{DeploymentDiff, MANIFEST_FILE},
+    layout::Layouts,
+    local,
+    manifest::Workspace,
+    probe,
+    request::HttpRequest,
+    scripting::Scripts,
+    shuttle::{
+        models::{DeploymentDetails, ProjectState, StateTransition},
+        Shuttle,
+    },
+    sql::{self, Connection},
     tab::Tab,
-    tui,
+    task::{TaskId, TaskManager},
+    template, tui,
+    utils::{git_commit_author, git_commit_range, git_show_file},
 };
 
+/// Interval for polling the logs of a deployment that is in progress.
+const LOGS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Longest interval between the polls of the logs after failed polls.
+const LOGS_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Number of failed polls in a row after which the logs are no longer followed.
+const LOGS_MAX_FAILURES: u32 = 5;
+
+/// Time after which a deployment that is still not listed is no longer followed.
+const LOGS_MISSING_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Minimum time between a start of the local run and a restart on file change.
+const LOCAL_RESTART_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Interval for polling the state of a project that is being provisioned.
+const PROVISION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Time to wait for the next key of a multi-key combination.
+const KEY_SEQUENCE_TIMEOUT: Duration = Duration::from_secs(1);
+
 pub struct App {
     pub shuttle: Shuttle,
     pub config: Config,
     pub tick_rate: f64,
     pub frame_rate: f64,
     pub components: Vec<Box<dyn Component>>,
+    pub tasks: TaskManager,
+    pub cache: Cache,
+    pub refresh_task: Option<TaskId>,
+    pub logs_task: Option<TaskId>,
+    pub local_task: Option<TaskId>,
+    pub local_watcher: Option<RecommendedWatcher>,
+    /// Whether the local run should be started again once it is stopped.
+    pub restart_local: bool,
+    pub local_started_at: Option<Instant>,
     pub should_quit: bool,
     pub should_suspend: bool,
     pub tab: Tab,
+    /// Project that is selected in the Projects tab.
+    pub project: Option<String>,
+    pub layouts: Layouts,
+    /// Index of the component that owns the keyboard.
+    pub focus: Option<usize>,
+    /// Tab to go back to when the logs are closed.
+    pub logs_return_tab: Option<Tab>,
     pub last_tick_key_events: Vec<KeyEvent>,
+    pub last_key_event_at: Option<Instant>,
+    pub scripts: Option<Scripts>,
+    pub scripts_watcher: Option<RecommendedWatcher>,
+    /// Connection of the SQL console, shared with the tasks that query it.
+    pub sql_connection: Arc<tokio::sync::Mutex<Option<Connection>>>,
+    pub sql_task: Option<TaskId>,
+    pub probe_task: Option<TaskId>,
+    pub last_probe_at: Option<Instant>,
+    pub last_refresh_at: Option<Instant>,
+    /// Whether the terminal has the focus, as far as it reports it.
+    pub focused: bool,
+    /// Whether the title of the window was saved before it was first replaced.
+    pub title_saved: bool,
+    /// Title of the window as it was set last.
+    pub title: Option<String>,
+    /// Title of the last deployment event while the terminal is in the background.
+    pub alert_title: Option<String>,
 }
 
 impl App {
@@ -33,7 +115,24 @@ impl App {
         let tab = Tabs::new();
         let home = Home::new();
         let projects = Projects::new();
+        let resource_graph = ResourceGraph::new();
+        let domains = Domains::new();
         let deployments = Deployments::new();
+        let deployment_details = DeploymentDetailsPage::new();
+        let deployment_diff = DeploymentDiffPane::new();
+        let logs = LogViewer::new();
+        let local = Local::new();
+        let dashboard = Dashboard::new();
+        let terminal = TerminalPane::new();
+        let tasks = Tasks::new();
+        let profiles = Profiles::new();
+        let new_project = NewProject::new();
+        let init = Init::new();
+        let sql_console = SqlConsole::new();
+        let request_runner = RequestRunner::new();
+        let command_output = CommandOutput::new();
+        let notifications = Notifications::new();
+        let cache = Cache::load(shuttle.profile());
         Ok(Self {
             shuttle,
             tick_rate: args.tick_rate,
@@ -42,13 +141,53 @@ impl App {
                 Box::new(tab),
                 Box::new(home),
                 Box::new(projects),
+                Box::new(resource_graph),
+                Box::new(domains),
                 Box::new(deployments),
+                Box::new(deployment_details),
+                Box::new(deployment_diff),
+                Box::new(logs),
+                Box::new(local),
+                Box::new(dashboard),
+                Box::new(tasks),
+                Box::new(profiles),
+                Box::new(init),
+                Box::new(new_project),
+                Box::new(sql_console),
+                Box::new(request_runner),
+                Box::new(command_output),
+                Box::new(terminal),
+                Box::new(notifications),
             ],
+            tasks: TaskManager::new(),
+            cache,
+            refresh_task: None,
+            logs_task: None,
+            local_task: None,
+            local_watcher: None,
+            restart_local: false,
+            local_started_at: None,
             should_quit: false,
             should_suspend: false,
             config,
             tab: Tab::Home,
+            project: None,
+            layouts: Layouts::load(),
+            focus: None,
+            logs_return_tab: None,
             last_tick_key_events: Vec::new(),
+            last_key_event_at: None,
+            scripts: None,
+            scripts_watcher: None,
+            sql_connection: Arc::default(),
+            sql_task: None,
+            probe_task: None,
+            last_probe_at: None,
+            last_refresh_at: None,
+            focused: true,
+            title_saved: false,
+            title: None,
+            alert_title: None,
         })
     }
 
@@ -68,42 +207,95 @@ impl App {
             component.register_config_handler(self.config.clone())?;
         }
 
+        self.tasks.register_action_handler(action_tx.clone());
+        for component in self.components.iter_mut() {
+            component.register_task_handler(self.tasks.clone())?;
+        }
+
         for component in self.components.iter_mut() {
             component.init()?;
         }
 
+        action_tx.send(Action::ProfileChanged(self.shuttle.profile().to_string()))?;
+        action_tx.send(Action::PlatformUpdated(Box::new(
+            self.cache.snapshot().clone(),
+        )))?;
+        action_tx.send(Action::Refresh)?;
+
+        match Workspace::detect(&std::env::current_dir()?) {
+            Ok(Some(workspace)) => {
+                action_tx.send(Action::WorkspaceDetected(Box::new(workspace)))?
+            }
+            Ok(None) => log::info!("The current directory is not a Cargo project"),
+            Err(e) => action_tx.send(Action::Error(e.to_string()))?,
+        }
+
+        self.load_scripts(&action_tx);
+        if let Some(scripts) = &mut self.scripts {
+            scripts.start(self.shuttle.profile(), self.cache.snapshot());
+        }
+
         loop {
             if let Some(e) = tui.next().await {
+                self.update_focus();
+                let capturing = self
+                    .focus
+                    .is_some_and(|i| self.components[i].captures_input());
+                // Keys that are part of a multi-key combination are not passed on.
+                let mut swallowed = false;
                 match e {
                     tui::Event::Quit => action_tx.send(Action::Quit)?,
                     tui::Event::Tick => action_tx.send(Action::Tick)?,
                     tui::Event::Render => action_tx.send(Action::Render)?,
                     tui::Event::Resize(x, y) => action_tx.send(Action::Resize(x, y))?,
+                    tui::Event::FocusLost => self.focused = false,
+                    tui::Event::FocusGained => {
+                        self.focused = true;
+                        self.alert_title = None;
+                    }
+                    // Only the binding of the terminal is kept while a component captures the input.
+                    tui::Event::Key(key) if capturing => {
+                        let scopes = self.key_scopes();
+                        if let Some(Action::ToggleTerminal) =
+                            self.config.keybindings.resolve(&scopes, &[key])
+                        {
+                            action_tx.send(Action::ToggleTerminal)?;
+                            swallowed = true;
+                        }
+                    }
                     tui::Event::Key(key) => {
-                        if let Some(keymap) = self.config.keybindings.get(&self.tab) {
-                            if let Some(action) = keymap.get(&vec![key.clone()]) {
-                                log::info!("Got action: {action:?}");
-                                action_tx.send(action.clone())?;
-                            } else {
-                                // If the key was not handled as a single key action,
-                                // then consider it for multi-key combinations.
-                                self.last_tick_key_events.push(key);
-
-                                // Check for multi-key combinations
-                                if let Some(action) = keymap.get(&self.last_tick_key_events) {
-                                    log::info!("Got action: {action:?}");
-                                    action_tx.send(action.clone())?;
-                                }
-                            }
-                        };
+                        let scopes = self.key_scopes();
+                        let keymap = &self.config.keybindings;
+                        self.last_tick_key_events.push(key);
+                        self.last_key_event_at = Some(Instant::now());
+                        if let Some(action) = keymap.resolve(&scopes, &self.last_tick_key_events) {
+                            log::info!("Got action: {action:?}");
+                            action_tx.send(action.clone())?;
+                            swallowed = self.last_tick_key_events.len() > 1;
+                            self.last_tick_key_events.clear();
+                        } else if keymap.is_prefix(&scopes, &self.last_tick_key_events) {
+                            // Wait for the rest of the combination.
+                            swallowed = true;
+                        } else {
+                            // A key that does not complete a combination is dropped.
+                            swallowed = self.last_tick_key_events.len() > 1;
+                            self.last_tick_key_events.clear();
+                        }
                     }
                     _ => {}
                 }
-                for component in self
-                    .components
-                    .iter_mut()
-                    .filter(|v| v.assigned_tab().is_none() || v.assigned_tab() == Some(self.tab))
-                {
+                // Keys only go to the focused component, while the other events
+                // go to every component that is shown.
+                let layout = self.layouts.get(self.tab);
+                let is_key = matches!(e, tui::Event::Key(_));
+                for (_, component) in self.components.iter_mut().enumerate().filter(|(i, v)| {
+                    if is_key {
+                        !swallowed && self.focus == Some(*i)
+                    } else {
+                        v.assigned_tab().is_none()
+                            || v.assigned_tab().is_some_and(|view| layout.is_visible(view))
+                    }
+                }) {
                     if let Some(action) = component.handle_events(Some(e.clone()))? {
                         action_tx.send(action)?;
                     }
@@ -114,60 +306,234 @@ impl App {
                 if action != Action::Tick && action != Action::Render {
                     log::debug!("{action:?}");
                 }
+                let tab = self.tab;
                 match action {
                     Action::Tick => {
-                        self.last_tick_key_events.drain(..);
+                        if !self
+                            .last_key_event_at
+                            .is_some_and(|v| v.elapsed() <= KEY_SEQUENCE_TIMEOUT)
+                        {
+                            self.last_tick_key_events.clear();
+                        }
+                        let interval = self.config.platform.refresh_interval;
+                        if is_due(self.last_refresh_at, interval) {
+                            self.refresh(true);
+                        }
+                        let interval = self.config.dashboard.probe_interval;
+                        if is_due(self.last_probe_at, interval) && self.probe_task.is_none() {
+                            self.probe_services();
+                        }
+                    }
+                    Action::Quit => {
+                        if self.tasks.is_busy() {
+                            action_tx.send(Action::ConfirmQuit)?;
+                        } else {
+                            self.should_quit = true;
+                        }
+                    }
+                    Action::ForceQuit => {
+                        self.tasks.cancel_all();
+                        self.should_quit = true;
+                    }
+                    Action::CancelTask(id) => {
+                        if !self.tasks.cancel(id) {
+                            log::warn!("No running task with id {id}");
+                        }
+                    }
+                    Action::TaskCompleted(id)
+                    | Action::TaskCancelled(id)
+                    | Action::TaskFailed(id, _) => {
+                        if self.refresh_task == Some(id) {
+                            self.refresh_task = None;
+                        }
+                        if self.logs_task == Some(id) {
+                            self.logs_task = None;
+                        }
+                        if self.sql_task == Some(id) {
+                            self.sql_task = None;
+                        }
+                        if self.probe_task == Some(id) {
+                            self.probe_task = None;
+                        }
+                        if self.local_task == Some(id) {
+                            self.local_task = None;
+                            if std::mem::take(&mut self.restart_local) {
+                                self.start_local(&action_tx);
+                            } else if matches!(action, Action::TaskCancelled(_)) {
+                                action_tx.send(Action::LocalStopped)?;
+                            }
+                        }
+                    }
+                    Action::Refresh => self.refresh(false),
+                    Action::CreateProject(ref name, idle_minutes) => {
+                        self.create_project(name, idle_minutes)
+                    }
+                    Action::OpenDeployment(ref project, ref id) => {
+                        self.fetch_deployment_details(project, id)
+                    }
+                    Action::CompareDeployments(ref project, ref from, ref to) => {
+                        self.compare_deployments(project, from, to, &action_tx)?
+                    }
+                    Action::OpenLogs(ref project, ref id) => {
+                        if !self.layouts.get(self.tab).is_visible(Tab::Logs) {
+                            self.logs_return_tab = Some(self.tab);
+                            self.tab = Tab::Logs;
+                        }
+                        self.follow_logs(project, id)
+                    }
+                    Action::CloseLogs => {
+                        if let Some(id) = self.logs_task.take() {
+                            self.tasks.cancel(id);
+                        }
+                        if let Some(tab) = self.logs_return_tab.take() {
+                            self.tab = tab;
+                        }
+                    }
+                    Action::AddDomain(ref project, ref domain) => self.add_domain(project, domain),
+                    Action::DeleteDomain(ref project, ref domain) => {
+                        self.delete_domain(project, domain)
+                    }
+                    Action::OpenSqlConsole(ref target) => self.connect_sql(target, &action_tx)?,
+                    Action::RunQuery(ref query) => self.run_query(query),
+                    Action::CloseSqlConsole => {
+                        // Cancels the connection or the running query, which
+                        // keeps its handle until the database gives up on it.
+                        if let Some(id) = self.sql_task.take() {
+                            self.tasks.cancel(id);
+                        }
+                        self.sql_connection = Arc::default();
+                    }
+                    Action::SendRequest(ref project, ref request) => {
+                        self.send_request(project, request, &action_tx)?
+                    }
+                    Action::Layout(command) => {
+                        if let Err(e) = self.layouts.apply(self.tab, command) {
+                            log::error!("Failed to save the layouts: {e}");
+                        }
+                    }
+                    Action::GoToTab(tab) => {
+                        self.tab = tab;
+                        self.logs_return_tab = None;
+                    }
+                    Action::RunCommand(ref command) => self.run_command(command),
+                    Action::Command(ref name) => self.run_user_command(name, &action_tx)?,
+                    Action::SelectProject(ref name) => self.project = Some(name.clone()),
+                    Action::FocusNext => self.cycle_focus(true),
+                    Action::FocusPrevious => self.cycle_focus(false),
+                    Action::Redeploy(ref project, ref id) => self.redeploy(project, id),
+                    Action::StartLocal => self.start_local(&action_tx),
+                    Action::RestartLocal => match self.local_task {
+                        Some(id) => {
+                            self.restart_local = true;
+                            self.tasks.cancel(id);
+                        }
+                        None => self.start_local(&action_tx),
+                    },
+                    Action::StopLocal => {
+                        self.restart_local = false;
+                        self.local_watcher = None;
+                        if let Some(id) = self.local_task {
+                            self.tasks.cancel(id);
+                        }
+                    }
+                    Action::LocalFilesChanged => {
+                        // Editors tend to emit a burst of events for a single save.
+                        let settled = !self
+                            .local_started_at
+                            .is_some_and(|v| v.elapsed() <= LOCAL_RESTART_DEBOUNCE);
+                        if self.local_task.is_some() && !self.restart_local && settled {
+                            log::info!("Restarting the local run after a file change");
+                            action_tx.send(Action::RestartLocal)?;
+                        }
+                    }
+                    Action::ScriptsChanged => self.load_scripts(&action_tx),
+                    Action::PlatformFetched(ref profile, ref state)
+                        if profile == self.shuttle.profile() =>
+                    {
+                        let alert = alert::poll_alert(
+                            &self.cache.snapshot().state,
+                            state,
+                            self.focused,
+                            &self.config.notifications,
+                        );
+                        alert::write(&alert.sequence);
+                        if alert.title.is_some() {
+                            self.alert_title = alert.title;
+                        }
+                        if let Err(e) = self.cache.update(*state.clone()) {
+                            log::error!("Failed to save the cache: {e}");
+                        }
+                        action_tx.send(Action::PlatformUpdated(Box::new(
+                            self.cache.snapshot().clone(),
+                        )))?;
+                    }
+                    Action::PlatformUnreachable(ref profile, ref e)
+                        if profile == self.shuttle.profile() =>
+                    {
+                        log::error!("Platform is unreachable: {e}");
+                        self.cache.set_offline();
+                        action_tx.send(Action::PlatformUpdated(Box::new(
+                            self.cache.snapshot().clone(),
+                        )))?;
                     }
-                    Action::Quit => self.should_quit = true,
                     Action::Suspend => self.should_suspend = true,
                     Action::Resume => self.should_suspend = false,
                     Action::Resize(w, h) => {
                         tui.resize(Rect::new(0, 0, w, h))?;
-                        tui.draw(|f| {
-                            for component in self.components.iter_mut().filter(|v| {
-                                v.assigned_tab().is_none() || v.assigned_tab() == Some(self.tab)
-                            }) {
-                                let r = component.draw(f, f.size());
-                                if let Err(e) = r {
-                                    action_tx
-                                        .send(Action::Error(format!("Failed to draw: {:?}", e)))
-                                        .unwrap();
-                                }
-                            }
-                        })?;
+                        tui.draw(|f| self.draw(f, &action_tx))?;
                     }
                     Action::Render => {
-                        tui.draw(|f| {
-                            for component in self.components.iter_mut().filter(|v| {
-                                v.assigned_tab().is_none() || v.assigned_tab() == Some(self.tab)
-                            }) {
-                                let r = component.draw(f, f.size());
-                                if let Err(e) = r {
-                                    action_tx
-                                        .send(Action::Error(format!("Failed to draw: {:?}", e)))
-                                        .unwrap();
+                        tui.draw(|f| self.draw(f, &action_tx))?;
+                    }
+                    Action::SwitchProfile(ref name) => {
+                        match Shuttle::from_profile(&self.config, Some(name)) {
+                            Ok(shuttle) => {
+                                if let Some(id) = self.refresh_task.take() {
+                                    self.tasks.cancel(id);
                                 }
+                                self.shuttle = shuttle;
+                                self.cache = Cache::load(self.shuttle.profile());
+                                action_tx.send(Action::ProfileChanged(name.clone()))?;
+                                action_tx.send(Action::PlatformUpdated(Box::new(
+                                    self.cache.snapshot().clone(),
+                                )))?;
+                                action_tx.send(Action::Refresh)?;
+                            }
+                            Err(e) => {
+                                log::error!("Failed to switch to profile {name}: {e}");
+                                action_tx.send(Action::Error(e.to_string()))?;
                             }
-                        })?;
+                        }
                     }
                     Action::NextTab => {
-                        self.tab = next_cycle(&self.tab).unwrap_or_default();
+                        self.tab = self.tab.next();
+                        self.logs_return_tab = None;
                     }
                     Action::PreviousTab => {
-                        self.tab = previous_cycle(&self.tab).unwrap_or_default();
+                        self.tab = self.tab.previous();
+                        self.logs_return_tab = None;
                     }
                     _ => {}
                 }
-                for component in self
-                    .components
-                    .iter_mut()
-                    .filter(|v| v.assigned_tab().is_none() || v.assigned_tab() == Some(self.tab))
-                {
+                let layout = self.layouts.get(self.tab);
+                for component in self.components.iter_mut().filter(|v| {
+                    v.assigned_tab().is_none()
+                        || v.assigned_tab().is_some_and(|view| layout.is_visible(view))
+                        || action.is_broadcast()
+                }) {
                     if let Some(action) = component.update(action.clone())? {
                         action_tx.send(action)?
                     };
                 }
+                if let Some(scripts) = &mut self.scripts {
+                    scripts.update(&action, self.tab);
+                }
+                if self.tab != tab {
+                    action_tx.send(Action::TabChanged(self.tab))?;
+                }
             }
+            self.update_focus();
+            self.update_title();
             if self.should_suspend {
                 tui.suspend()?;
                 action_tx.send(Action::Resume)?;
@@ -180,7 +546,802 @@ impl App {
                 break;
             }
         }
+        if self.title_saved {
+            alert::write(alert::POP_TITLE);
+        }
         tui.exit()?;
         Ok(())
     }
+
+    /// Loads the scripts of the config directory, which is watched for
+    /// changes on the first load.
+    fn load_scripts(&mut self, action_tx: &UnboundedSender<Action>) {
+        let directory = Scripts::directory();
+        if self.scripts_watcher.is_none() {
+            match local::watch(
+                std::slice::from_ref(&directory),
+                action_tx.clone(),
+                Action::ScriptsChanged,
+            ) {
+                Ok(watcher) => self.scripts_watcher = Some(watcher),
+                Err(e) => log::error!("Failed to watch {}: {e}", directory.display()),
+            }
+        }
+        self.scripts
+            .get_or_insert_with(|| Scripts::new(action_tx.clone()))
+            .load_from(&directory);
+    }
+
+    /// Runs a command line in the background.
+    ///
+    /// The output is logged, while a failure is reported by the task.
+    fn run_command(&mut self, command: &str) {
+        let command = command.to_string();
+        self.tasks
+            .spawn(format!("Run `{command}`"), |ctx| async move {
+                let output = local::run(&command).await?;
+                log::info!("Output of `{command}`:\n{output}");
+                Ok(())
+            });
+    }
+
+    /// Runs a command of the `commands` section of the configuration.
+    ///
+    /// The actions are sent first, and then the command line is run in the
+    /// background with the variables of the selected project.
+    fn run_user_command(&mut self, name: &str, action_tx: &UnboundedSender<Action>) -> Result<()> {
+        let Some(command) = self.config.commands.get(name).cloned() else {
+            action_tx.send(Action::Error(format!("Unknown command `{name}`")))?;
+            return Ok(());
+        };
+        for action in command.actions {
+            action_tx.send(action)?;
+        }
+        let Some(run) = command.run else {
+            return Ok(());
+        };
+        let service = self
+            .project
+            .as_ref()
+            .and_then(|project| self.cache.snapshot().state.services.get(project));
+        // The values come from the API, so they are quoted to keep them from
+        // being run as part of the command line.
+        let variables = [
+            ("project", self.project.clone()),
+            (
+                "deployment_id",
+                service.and_then(|v| v.deployment.as_ref().map(|v| v.id.clone())),
+            ),
+            ("service_url", service.map(|v| v.uri.clone())),
+        ]
+        .map(|(name, value)| (name, value.as_deref().map(template::shell_quote)));
+        let line = match template::render(&run, &variables) {
+            Ok(line) => line,
+            Err(e) => {
+                action_tx.send(Action::Error(format!("Cannot run `{name}`: {e}")))?;
+                return Ok(());
+            }
+        };
+        let name = name.to_string();
+        self.tasks
+            .spawn(format!("Run {name}"), move |ctx| async move {
+                match local::run(&line).await {
+                    Ok(output) => match command.output {
+                        OutputMode::Popup => ctx.send(Action::ShowOutput(name, output)),
+                        OutputMode::Notification => {
+                            let last_line = output.lines().rfind(|v| !v.trim().is_empty());
+                            ctx.send(Action::Notify(match last_line {
+                                Some(line) => format!("{name}: {line}"),
+                                None => format!("{name} finished"),
+                            }));
+                        }
+                    },
+                    Err(e) => {
+                        ctx.send(Action::Error(e.to_string()));
+                        return Err(e);
+                    }
+                }
+                Ok(())
+            });
+        Ok(())
+    }
+
+    /// Returns the scopes of the key bindings, from the innermost to the
+    /// outermost.
+    fn key_scopes(&self) -> Vec<Scope> {
+        let mut scopes = Vec::with_capacity(3);
+        if let Some(name) = self.focus.and_then(|i| self.components[i].key_scope()) {
+            scopes.push(Scope::from(name));
+        }
+        scopes.push(Scope::Tab(self.layouts.get(self.tab).focused()));
+        scopes.push(Scope::Global);
+        scopes
+    }
+
+    /// Gives the keyboard to a single component.
+    ///
+    /// Popups that are open take precedence over the panes. Otherwise the
+    /// focus stays in the focused pane, preferring the component that had it.
+    fn update_focus(&mut self) {
+        let view = self.layouts.get(self.tab).focused();
+        let in_view = |v: &dyn Component| v.assigned_tab() == Some(view) && v.focusable();
+        let focus = self
+            .components
+            .iter()
+            .rposition(|v| v.assigned_tab().is_none() && v.focusable())
+            .or_else(|| self.focus.filter(|i| in_view(self.components[*i].as_ref())))
+            .or_else(|| self.components.iter().position(|v| in_view(v.as_ref())));
+        if focus != self.focus {
+            if let Some(i) = self.focus {
+                self.components[i].set_focused(false);
+            }
+            if let Some(i) = focus {
+                self.components[i].set_focused(true);
+            }
+            self.focus = focus;
+        }
+    }
+
+    /// Moves the focus to the next focusable component in the panes that
+    /// are shown, or to the previous one.
+    fn cycle_focus(&mut self, forward: bool) {
+        if self
+            .focus
+            .is_some_and(|i| self.components[i].assigned_tab().is_none())
+        {
+            return;
+        }
+        let layout = self.layouts.get(self.tab);
+        let candidates = layout
+            .views()
+            .into_iter()
+            .enumerate()
+            .filter(|(pane, _)| !layout.zoomed || *pane == layout.focus)
+            .flat_map(|(pane, view)| {
+                self.components
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, v)| v.assigned_tab() == Some(view) && v.focusable())
+                    .map(move |(i, _)| (pane, i))
+            })
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return;
+        }
+        let len = candidates.len();
+        let next = match candidates.iter().position(|(_, i)| self.focus == Some(*i)) {
+            Some(current) if forward => (current + 1) % len,
+            Some(current) => (current + len - 1) % len,
+            None if forward => 0,
+            None => len - 1,
+        };
+        let (pane, component) = candidates[next];
+        if let Err(e) = self.layouts.select(self.tab, pane) {
+            log::error!("Failed to save the layouts: {e}");
+        }
+        if let Some(i) = self.focus {
+            self.components[i].set_focused(false);
+        }
+        self.components[component].set_focused(true);
+        self.focus = Some(component);
+    }
+
+    /// Draws the panes of the current tab, followed by the components that
+    /// are not assigned to a tab.
+    fn draw(&mut self, f: &mut Frame<'_>, action_tx: &UnboundedSender<Action>) {
+        let layout = self.layouts.get(self.tab);
+        let panes = layout.areas(f.size().inner(&Margin {
+            horizontal: 3,
+            vertical: 3,
+        }));
+        let draw = |component: &mut Box<dyn Component>, f: &mut Frame<'_>, area: Rect| {
+            if let Err(e) = component.draw(f, area) {
+                action_tx
+                    .send(Action::Error(format!("Failed to draw: {:?}", e)))
+                    .unwrap();
+            }
+        };
+        for (view, area) in &panes {
+            for component in self
+                .components
+                .iter_mut()
+                .filter(|v| v.assigned_tab() == Some(*view))
+            {
+                draw(component, f, *area);
+            }
+        }
+        if layout.views().len() > 1 {
+            if let Some((_, area)) = panes.iter().find(|(view, _)| *view == layout.focused()) {
+                let mut block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Yellow));
+                if layout.zoomed {
+                    block = block.title(
+                        block::Title::from("zoomed")
+                            .position(block::Position::Bottom)
+                            .alignment(Alignment::Left),
+                    );
+                }
+                f.render_widget(block, *area);
+            }
+        }
+        for component in self
+            .components
+            .iter_mut()
+            .filter(|v| v.assigned_tab().is_none())
+        {
+            draw(component, f, f.size());
+        }
+    }
+
+    /// Fetches the details of a deployment in the background.
+    ///
+    /// The result is sent back as `Action::DeploymentDetailsFetched`.
+    fn fetch_deployment_details(&mut self, project: &str, id: &str) {
+        let shuttle = self.shuttle.clone();
+        let state = &self.cache.snapshot().state;
+        let commit = state
+            .deployments(project)
+            .iter()
+            .find(|deployment| deployment.id == id)
+            .and_then(|deployment| deployment.git_commit_id.clone());
+        let uri = state
+            .services
+            .get(project)
+            .map(|service| service.uri.clone());
+        let project = project.to_string();
+        let id = id.to_string();
+        let name = format!("Fetch details of {}", id.get(..7).unwrap_or(&id));
+        self.tasks.spawn(name, |ctx| async move {
+            ctx.progress(None, "Fetching logs");
+            // The rest of the details are still shown if the logs cannot be fetched.
+            let transitions = match shuttle.get_logs(&project, &id).await {
+                Ok(logs) => StateTransition::from_logs(&logs),
+                Err(e) => {
+                    log::warn!("Failed to fetch the logs of {id}: {e}");
+                    Vec::new()
+                }
+            };
+            ctx.progress(None, "Checking health");
+            let health_check = match uri {
+                Some(uri) => Some(shuttle.check_health(&uri).await),
+                None => None,
+            };
+            let author = match commit {
+                Some(commit) => git_commit_author(&commit).await,
+                None => None,
+            };
+            ctx.send(Action::DeploymentDetailsFetched(Box::new(
+                DeploymentDetails {
+                    project,
+                    deployment_id: id,
+                    transitions,
+                    author,
+                    health_check,
+                },
+            )));
+            Ok(())
+        });
+    }
+
+    /// Compares two deployments of a project in the background.
+    ///
+    /// The resources are taken from the cache, while the commits and the
+    /// manifests are read from the local repository.
+    fn compare_deployments(
+        &mut self,
+        project: &str,
+        from: &str,
+        to: &str,
+        action_tx: &UnboundedSender<Action>,
+    ) -> Result<()> {
+        let state = &self.cache.snapshot().state;
+        let (Some(from), Some(to)) = (
+            state.deployment(project, from).cloned(),
+            state.deployment(project, to).cloned(),
+        ) else {
+            log::warn!("Cannot compare unknown deployments of {project}");
+            action_tx.send(Action::Error(format!(
+                "Cannot compare unknown deployments of {project}"
+            )))?;
+            action_tx.send(Action::CloseComparison)?;
+            return Ok(());
+        };
+        let (from, to) = if from.last_update <= to.last_update {
+            (from, to)
+        } else {
+            (to, from)
+        };
+        let resources =
+            [&from, &to].map(|deployment| state.deployment_resources.get(&deployment.id).cloned());
+        let project = project.to_string();
+        let name = format!("Compare {} with {}", from.short_id(), to.short_id());
+        self.tasks.spawn(name, |ctx| async move {
+            ctx.progress(None, "Reading the repository");
+            let (commits, old, new) = match (&from.git_commit_id, &to.git_commit_id) {
+                (Some(old), Some(new)) => tokio::join!(
+                    git_commit_range(old, new),
+                    git_show_file(old, MANIFEST_FILE),
+                    git_show_file(new, MANIFEST_FILE)
+                ),
+                _ => (None, None, None),
+            };
+            let [old_resources, new_resources] = &resources;
+            let diff = DeploymentDiff::new(
+                project,
+                [from, to],
+                [old_resources.as_deref(), new_resources.as_deref()],
+                [old, new],
+                commits,
+            );
+            ctx.send(Action::DeploymentsCompared(Box::new(diff)));
+            Ok(())
+        });
+        Ok(())
+    }
+
+    /// Streams the logs of a deployment to the log viewer.
+    ///
+    /// The logs are polled until the deployment reaches a final state, after
+    /// which the platform state is refreshed if it changed in the meantime.
+    fn follow_logs(&mut self, project: &str, id: &str) {
+        if let Some(task) = self.logs_task.take() {
+            self.tasks.cancel(task);
+        }
+        let shuttle = self.shuttle.clone();
+        let project = project.to_string();
+        let id = id.to_string();
+        let name = format!("Follow logs of {}", id.get(..7).unwrap_or(&id));
+        let task = self.tasks.spawn(name, |ctx| async move {
+            let token = ctx.cancellation_token();
+            let mut in_progress = false;
+            let mut failures = 0;
+            let mut missing_since = None;
+            loop {
+                let (logs, deployments) = tokio::join!(
+                    shuttle.get_logs(&project, &id),
+                    shuttle.get_deployments(&project)
+                );
+                match (logs, deployments) {
+                    (Ok(logs), Ok(deployments)) => {
+                        failures = 0;
+                        let state = deployments
+                            .into_iter()
+                            .find(|deployment| deployment.id == id)
+                            .map(|deployment| deployment.state);
+                        ctx.send(Action::LogsFetched(project.clone(), id.clone(), logs));
+                        match state {
+                            Some(state) => {
+                                ctx.progress(None, state.to_string());
+                                missing_since = None;
+                                if !state.is_in_progress() {
+                                    break;
+                                }
+                            }
+                            // A new deployment might not be listed yet, so keep
+                            // polling until it is, for a while.
+                            None => {
+                                let since = *missing_since.get_or_insert_with(Instant::now);
+                                if since.elapsed() >= LOGS_MISSING_TIMEOUT {
+                                    bail!("Deployment {id} is not listed");
+                                }
+                            }
+                        }
+                        in_progress = true;
+                    }
+                    // A failed poll is retried after a growing delay.
+                    (Err(e), _) | (_, Err(e)) => {
+                        failures += 1;
+                        if failures >= LOGS_MAX_FAILURES {
+                            return Err(e.wrap_err(format!("Failed to poll the logs of {id}")));
+                        }
+                        log::warn!("Failed to poll the logs of {id}: {e}");
+                        ctx.progress(None, "Retrying");
+                    }
+                }
+                tokio::select! {
+                    _ = token.cancelled() => return Ok(()),
+                    _ = tokio::time::sleep(logs_poll_delay(failures)) => {}
+                }
+            }
+            if in_progress {
+                ctx.send(Action::Refresh);
+            }
+            Ok(())
+        });
+        self.logs_task = Some(task);
+    }
+
+    /// Starts the local run unless it is already running.
+    ///
+    /// The watched paths are only set up on the first start, so that they
+    /// keep triggering restarts until the run is stopped.
+    fn start_local(&mut self, action_tx: &mpsc::UnboundedSender<Action>) {
+        if self.local_task.is_some() {
+            return;
+        }
+        let local = &self.config.local;
+        if local.restart_on_change && self.local_watcher.is_none() {
+            match local::watch(&local.watch, action_tx.clone(), Action::LocalFilesChanged) {
+                Ok(watcher) => self.local_watcher = Some(watcher),
+                Err(e) => log::error!("Failed to watch {:?}: {e}", local.watch),
+            }
+        }
+        let command = local.command.clone();
+        self.local_started_at = Some(Instant::now());
+        self.local_task = Some(self.tasks.spawn(format!("Run `{command}`"), |ctx| {
+            local::supervise(command, ctx)
+        }));
+    }
+
+    /// Creates a project and follows its state until it is provisioned.
+    fn create_project(&mut self, name: &str, idle_minutes: u64) {
+        let shuttle = self.shuttle.clone();
+        let name = name.to_string();
+        self.tasks
+            .spawn(format!("Create {name}"), |ctx| async move {
+                let token = ctx.cancellation_token();
+                ctx.progress(None, "Requesting the project");
+                let mut project = match shuttle.create_project(&name, idle_minutes).await {
+                    Ok(project) => project,
+                    Err(e) => {
+                        ctx.send(Action::ProjectCreationFailed(name, e.to_string()));
+                        return Err(e);
+                    }
+                };
+                loop {
+                    ctx.progress(None, project.state.to_string());
+                    ctx.send(Action::ProjectProvisioning(project.clone()));
+                    if !project.state.is_pending() {
+                        break;
+                    }
+                    tokio::select! {
+                        _ = token.cancelled() => return Ok(()),
+                        _ = tokio::time::sleep(PROVISION_POLL_INTERVAL) => {}
+                    }
+                    project = match shuttle.get_project(&name).await {
+                        Ok(project) => project,
+                        Err(e) => {
+                            ctx.send(Action::ProjectCreationFailed(name, e.to_string()));
+                            return Err(e);
+                        }
+                    };
+                }
+                ctx.send(Action::Refresh);
+                if let ProjectState::Errored(message) = project.state {
+                    bail!("{name} failed to provision: {message}");
+                }
+                Ok(())
+            });
+    }
+
+    /// Probes the running services in the background for the Dashboard tab.
+    ///
+    /// Each result is sent as `Action::ServiceProbed`.
+    fn probe_services(&mut self) {
+        self.last_probe_at = Some(Instant::now());
+        let targets = probe::targets(&self.cache.snapshot().state);
+        if targets.is_empty() {
+            return;
+        }
+        let shuttle = self.shuttle.clone();
+        let task = self
+            .tasks
+            .spawn_background("Probe services", |ctx| async move {
+                let probes = targets.into_iter().map(|(project, id, uri)| {
+                    let shuttle = &shuttle;
+                    let ctx = &ctx;
+                    async move {
+                        let probe = tokio::time::timeout(
+                            probe::PROBE_TIMEOUT,
+                            probe::probe(shuttle, project.clone(), id.clone(), &uri),
+                        )
+                        .await
+                        .unwrap_or_else(|_| probe::timed_out(project, id, &uri));
+                        ctx.send(Action::ServiceProbed(Box::new(probe)));
+                    }
+                });
+                futures::future::join_all(probes).await;
+                Ok(())
+            });
+        self.probe_task = Some(task);
+    }
+
+    /// Adds a custom domain to a project and refreshes the platform state.
+    fn add_domain(&mut self, project: &str, domain: &str) {
+        let shuttle = self.shuttle.clone();
+        let project = project.to_string();
+        let domain = domain.to_string();
+        self.tasks
+            .spawn(format!("Add {domain} to {project}"), |ctx| async move {
+                ctx.progress(None, "Requesting the certificate");
+                let certificate = shuttle.add_certificate(&project, &domain).await?;
+                log::info!("Added {} to {project}", certificate.subject);
+                ctx.send(Action::Notify(format!(
+                    "Added {} to {project}",
+                    certificate.subject
+                )));
+                ctx.send(Action::Refresh);
+                Ok(())
+            });
+    }
+
+    /// Deletes a custom domain of a project and refreshes the platform state.
+    fn delete_domain(&mut self, project: &str, domain: &str) {
+        let shuttle = self.shuttle.clone();
+        let project = project.to_string();
+        let domain = domain.to_string();
+        self.tasks.spawn(
+            format!("Delete {domain} from {project}"),
+            |ctx| async move {
+                shuttle.delete_certificate(&project, &domain).await?;
+                log::info!("Deleted {domain} from {project}");
+                ctx.send(Action::Notify(format!("Deleted {domain} from {project}")));
+                ctx.send(Action::Refresh);
+                Ok(())
+            },
+        );
+    }
+
+    /// Connects the SQL console to the database of a project, or to a URL.
+    ///
+    /// The connection string of a project is looked up in the cache, so that
+    /// it does not go through the actions.
+    fn connect_sql(&mut self, target: &str, action_tx: &UnboundedSender<Action>) -> Result<()> {
+        let url = self
+            .cache
+            .snapshot()
+            .state
+            .resources(target)
+            .iter()
+            .find_map(sql::connection_string)
+            .or_else(|| target.contains(':').then(|| target.to_string()));
+        let Some(url) = url else {
+            let message = format!("{target} has no provisioned database");
+            log::warn!("{message}");
+            action_tx.send(Action::QueryFailed(message))?;
+            return Ok(());
+        };
+        if let Some(id) = self.sql_task.take() {
+            self.tasks.cancel(id);
+        }
+        let connection = Arc::new(tokio::sync::Mutex::new(None));
+        self.sql_connection = Arc::clone(&connection);
+        let task = self
+            .tasks
+            .spawn(format!("Connect to {target}"), |ctx| async move {
+                ctx.progress(None, "Connecting");
+                let token = ctx.cancellation_token();
+                let connected = tokio::select! {
+                    _ = token.cancelled() => return Ok(()),
+                    connected = Connection::connect(&url) => connected,
+                };
+                match connected {
+                    Ok(database) => {
+                        let engine = database.engine();
+                        *connection.lock().await = Some(database);
+                        ctx.send(Action::SqlConnected(engine.to_string()));
+                        Ok(())
+                    }
+                    Err(e) => {
+                        ctx.send(Action::QueryFailed(e.to_string()));
+                        Err(e)
+                    }
+                }
+            });
+        self.sql_task = Some(task);
+        Ok(())
+    }
+
+    /// Runs a query of the SQL console in the background.
+    fn run_query(&mut self, query: &str) {
+        let connection = Arc::clone(&self.sql_connection);
+        let query = query.to_string();
+        let task = self.tasks.spawn("Run query", |ctx| async move {
+            let connection = connection.lock().await;
+            let Some(connection) = connection.as_ref() else {
+                ctx.send(Action::QueryFailed(String::from("Not connected")));
+                bail!("The SQL console is not connected");
+            };
+            match connection.query(&query, &ctx.cancellation_token()).await {
+                Ok(result) => {
+                    ctx.send(Action::QueryFinished(Box::new(result)));
+                    Ok(())
+                }
+                Err(e) => {
+                    ctx.send(Action::QueryFailed(e.to_string()));
+                    Err(e)
+                }
+            }
+        });
+        self.sql_task = Some(task);
+    }
+
+    /// Sets the title of the window to the state of the selected project, or
+    /// to the last deployment event while the terminal is in the background.
+    fn update_title(&mut self) {
+        let title = match &self.alert_title {
+            Some(title) if !self.focused => title.clone(),
+            _ => alert::window_title(self.project.as_deref(), &self.cache.snapshot().state),
+        };
+        if self.title.as_ref() == Some(&title) {
+            return;
+        }
+        let mut sequence = String::new();
+        if !self.title_saved {
+            self.title_saved = true;
+            sequence.push_str(alert::PUSH_TITLE);
+        }
+        sequence.push_str(&alert::title_sequence(&title));
+        alert::write(&sequence);
+        self.title = Some(title);
+    }
+
+    /// Sends a request to the service of a project in the background.
+    fn send_request(
+        &mut self,
+        project: &str,
+        request: &HttpRequest,
+        action_tx: &UnboundedSender<Action>,
+    ) -> Result<()> {
+        let uri = self
+            .cache
+            .snapshot()
+            .state
+            .services
+            .get(project)
+            .map(|service| service.uri.clone())
+            .filter(|uri| !uri.is_empty());
+        let Some(uri) = uri else {
+            action_tx.send(Action::RequestFailed(
+                request.name.clone(),
+                format!("{project} has no running service"),
+            ))?;
+            return Ok(());
+        };
+        let shuttle = self.shuttle.clone();
+        let request = request.clone();
+        let name = format!("{} {}", request.method, request.path);
+        self.tasks.spawn(name, |ctx| async move {
+            match shuttle.send_request(&request.url(&uri), &request).await {
+                Ok(response) => {
+                    ctx.send(Action::RequestCompleted(request.name, Box::new(response)));
+                    Ok(())
+                }
+                Err(e) => {
+                    ctx.send(Action::RequestFailed(request.name, e.to_string()));
+                    Err(e)
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Redeploys the build of a deployment and opens its logs.
+    fn redeploy(&mut self, project: &str, id: &str) {
+        let shuttle = self.shuttle.clone();
+        let project = project.to_string();
+        let id = id.to_string();
+        let name = format!("Redeploy {}", id.get(..7).unwrap_or(&id));
+        self.tasks.spawn(name, |ctx| async move {
+            ctx.progress(None, "Requesting the redeploy");
+            let deployment = shuttle.redeploy(&project, &id).await?;
+            log::info!("Redeploying {id} of {project} as {}", deployment.id);
+            ctx.send(Action::OpenLogs(project, deployment.id));
+            Ok(())
+        });
+    }
+
+    /// Fetches the state of the platform in the background.
+    ///
+    /// The result is sent back as `Action::PlatformFetched`, or as
+    /// `Action::PlatformUnreachable` if the API could not be reached. The
+    /// periodic refreshes run in the background.
+    fn refresh(&mut self, background: bool) {
+        if self.refresh_task.is_some() {
+            return;
+        }
+        self.last_refresh_at = Some(Instant::now());
+        let shuttle = self.shuttle.clone();
+        let profile = shuttle.profile().to_string();
+        let id = self
+            .tasks
+            .spawn_task("Refresh platform state", background, |ctx| async move {
+                ctx.progress(None, "Fetching projects");
+                let projects = match shuttle.get_projects().await {
+                    Ok(projects) => projects,
+                    Err(e) => {
+                        ctx.send(Action::PlatformUnreachable(profile, e.to_string()));
+                        return Err(e);
+                    }
+                };
+                let mut state = PlatformState {
+                    projects: projects.clone(),
+                    ..Default::default()
+                };
+                match shuttle.get_version().await {
+                    Ok(version) => state.version = Some(version),
+                    Err(e) => log::warn!("Failed to fetch the platform version: {e}"),
+                }
+                for (i, project) in projects.iter().enumerate() {
+                    ctx.progress(
+                        Some(i as f64 / projects.len() as f64),
+                        format!("Fetching {}", project.name),
+                    );
+                    let name = &project.name;
+                    let (service, deployments, resources, certificates) = tokio::join!(
+                        shuttle.get_service(name),
+                        shuttle.get_deployments(name),
+                        shuttle.get_resources(name),
+                        shuttle.get_certificates(name)
+                    );
+                    match service {
+                        Ok(service) => {
+                            state.services.insert(name.clone(), service);
+                        }
+                        Err(e) => log::debug!("No service for {name}: {e}"),
+                    }
+                    match deployments {
+                        Ok(deployments) => {
+                            state.deployments.insert(name.clone(), deployments);
+                        }
+                        Err(e) => log::warn!("Failed to fetch the deployments of {name}: {e}"),
+                    }
+                    match resources {
+                        Ok(resources) => {
+                            state.resources.insert(name.clone(), resources);
+                        }
+                        Err(e) => log::warn!("Failed to fetch the resources of {name}: {e}"),
+                    }
+                    match certificates {
+                        Ok(certificates) => {
+                            state.certificates.insert(name.clone(), certificates);
+                        }
+                        Err(e) => log::warn!("Failed to fetch the certificates of {name}: {e}"),
+                    }
+                }
+                ctx.send(Action::PlatformFetched(profile, Box::new(state)));
+                Ok(())
+            });
+        self.refresh_task = Some(id);
+    }
+}
+
+/// Returns the delay before the next poll of the logs, which doubles with
+/// every failed poll in a row.
+fn logs_poll_delay(failures: u32) -> Duration {
+    LOGS_POLL_INTERVAL
+        .saturating_mul(2u32.saturating_pow(failures))
+        .min(LOGS_MAX_BACKOFF)
+}
+
+/// Returns `true` if an operation that runs every `interval` seconds and ran
+/// last at `last` is due again. An interval of 0 disables the operation.
+fn is_due(last: Option<Instant>, interval: u64) -> bool {
+    interval > 0 && !last.is_some_and(|v| v.elapsed() < Duration::from_secs(interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_due() {
+        assert!(is_due(None, 30));
+        assert!(!is_due(None, 0));
+        assert!(!is_due(Some(Instant::now()), 30));
+        assert!(is_due(
+            Instant::now().checked_sub(Duration::from_secs(31)),
+            30
+        ));
+    }
+
+    #[test]
+    fn test_logs_poll_delay() {
+        assert_eq!(logs_poll_delay(0), LOGS_POLL_INTERVAL);
+        assert_eq!(logs_poll_delay(1), LOGS_POLL_INTERVAL * 2);
+        assert_eq!(logs_poll_delay(2), LOGS_POLL_INTERVAL * 4);
+        assert_eq!(logs_poll_delay(40), LOGS_MAX_BACKOFF);
+    }
 }
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 use crate::utils::version;
 
@@ -24,4 +24,85 @@ pub struct Args {
         default_value_t = 60.0
     )]
     pub frame_rate: f64,
+
+    #[arg(
+        short = 'P',
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Profile to use from the configuration"
+    )]
+    pub profile: Option<String>,
+
+    #[arg(long, global = true, help = "Print the output of subcommands as JSON")]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Subcommands that run without starting the terminal user interface.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    #[command(about = "Manage projects")]
+    Projects {
+        #[command(subcommand)]
+        command: ProjectsCommand,
+    },
+    #[command(about = "Manage deployments")]
+    Deployments {
+        #[command(subcommand)]
+        command: DeploymentsCommand,
+    },
+    #[command(about = "Print the logs of a deployment")]
+    Logs {
+        #[arg(short, long, help = "Name of the project")]
+        project: String,
+
+        #[arg(help = "ID of the deployment (defaults to the latest one)")]
+        id: Option<String>,
+
+        #[arg(short, long, help = "Keep printing new logs as they arrive")]
+        follow: bool,
+    },
+    #[command(about = "Print the status of the platform")]
+    Status,
+    #[command(about = "Manage the configuration")]
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProjectsCommand {
+    #[command(about = "List the projects")]
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DeploymentsCommand {
+    #[command(about = "List the deployments of a project")]
+    List {
+        #[arg(short, long, help = "Name of the project")]
+        project: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    #[command(about = "Validate the configuration file")]
+    Check,
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn test_args() {
+        Args::command().debug_assert();
+    }
 }
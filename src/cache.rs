@@ -0,0 +1,437 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    shuttle::{
+        models::{Certificate, Deployment, Project, ProjectState, Resource, Service},
+        DEFAULT_PROFILE,
+    },
+    utils::{get_data_dir, humanize_duration},
+};
+
+/// Name of the file that the platform state is cached in.
+pub const CACHE_FILE: &str = "cache.json";
+
+/// Last known state of the platform.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlatformState {
+    #[serde(default)]
+    pub projects: Vec<Project>,
+    #[serde(default)]
+    pub services: BTreeMap<String, Service>,
+    #[serde(default)]
+    pub deployments: BTreeMap<String, Vec<Deployment>>,
+    #[serde(default)]
+    pub resources: BTreeMap<String, Vec<Resource>>,
+    /// Resources that were provisioned while a deployment was running, by deployment ID.
+    ///
+    /// Only the names of their values are kept, which is enough to compare
+    /// the secrets of two deployments.
+    #[serde(default)]
+    pub deployment_resources: BTreeMap<String, Vec<Resource>>,
+    /// Version of the platform, which is the runtime version that it supports.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Certificates of the custom domains, by project.
+    #[serde(default)]
+    pub certificates: BTreeMap<String, Vec<Certificate>>,
+}
+
+impl PlatformState {
+    pub fn project(&self, name: &str) -> Option<&Project> {
+        self.projects.iter().find(|project| project.name == name)
+    }
+
+    pub fn deployments(&self, project: &str) -> &[Deployment] {
+        self.deployments
+            .get(project)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn resources(&self, project: &str) -> &[Resource] {
+        self.resources
+            .get(project)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn certificates(&self, project: &str) -> &[Certificate] {
+        self.certificates
+            .get(project)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn deployment(&self, project: &str, id: &str) -> Option<&Deployment> {
+        self.deployments(project)
+            .iter()
+            .find(|deployment| deployment.id == id)
+    }
+
+    /// Returns the state of a project, or of its deployments once it is ready.
+    ///
+    /// A deployment that is in progress takes precedence over the one that
+    /// is running.
+    pub fn project_status(&self, name: &str) -> Option<String> {
+        let project = self.project(name)?;
+        if project.state != ProjectState::Ready {
+            return Some(project.state.to_string());
+        }
+        let deployment = self
+            .deployments(name)
+            .iter()
+            .filter(|deployment| deployment.state.is_in_progress())
+            .max_by_key(|deployment| deployment.last_update)
+            .or_else(|| self.services.get(name)?.deployment.as_ref());
+        Some(match deployment {
+            Some(deployment) => deployment.state.to_string(),
+            None => project.state.to_string(),
+        })
+    }
+
+    /// Merges freshly fetched state into this one.
+    ///
+    /// The project list is always replaced, while the per-project entries are
+    /// only replaced if they were fetched. Entries of projects that no longer
+    /// exist are dropped.
+    ///
+    /// The resources of a project are also recorded for the deployment that is
+    /// currently running, so that older deployments can be compared later on.
+    pub fn reconcile(&mut self, fresh: PlatformState) {
+        let PlatformState {
+            projects,
+            services,
+            deployments,
+            resources,
+            deployment_resources,
+            version,
+            certificates,
+        } = fresh;
+        if version.is_some() {
+            self.version = version;
+        }
+        let redacted = |resources: &[Resource]| resources.iter().map(Resource::redacted).collect();
+        self.deployment_resources.extend(
+            deployment_resources
+                .iter()
+                .map(|(id, resources)| (id.clone(), redacted(resources))),
+        );
+        for (name, service) in &services {
+            if let (Some(deployment), Some(resources)) = (&service.deployment, resources.get(name))
+            {
+                self.deployment_resources
+                    .insert(deployment.id.clone(), redacted(resources));
+            }
+        }
+        let exists = |name: &String| projects.iter().any(|project| &project.name == name);
+        self.services.retain(|name, _| exists(name));
+        self.deployments.retain(|name, _| exists(name));
+        self.resources.retain(|name, _| exists(name));
+        self.certificates.retain(|name, _| exists(name));
+        self.services.extend(services);
+        self.deployments.extend(deployments);
+        self.resources.extend(resources);
+        self.certificates.extend(certificates);
+        self.projects = projects;
+        let known = self
+            .deployments
+            .values()
+            .flatten()
+            .map(|deployment| deployment.id.clone())
+            .collect::<Vec<_>>();
+        self.deployment_resources.retain(|id, _| known.contains(id));
+    }
+}
+
+/// Snapshot of the platform state that is shared with the components.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub state: PlatformState,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Whether the state was loaded from the cache and not reconciled yet.
+    #[serde(skip)]
+    pub stale: bool,
+    /// Whether the API was unreachable on the last refresh.
+    #[serde(skip)]
+    pub offline: bool,
+}
+
+impl Snapshot {
+    /// Returns `true` if operations that modify the platform should be disabled.
+    pub fn is_read_only(&self) -> bool {
+        self.offline
+    }
+
+    /// Returns a short description of the freshness of the data, if it is not up-to-date.
+    pub fn status(&self) -> Option<String> {
+        let age = self
+            .updated_at
+            .map(|v| format!(", cached {} ago", humanize_duration(Utc::now() - v)))
+            .unwrap_or_default();
+        if self.offline {
+            Some(format!("offline, read-only{age}"))
+        } else if self.stale {
+            Some(format!("stale{age}"))
+        } else {
+            None
+        }
+    }
+}
+
+/// Cache of the platform state that is persisted to the data directory.
+#[derive(Debug, Default)]
+pub struct Cache {
+    path: PathBuf,
+    snapshot: Snapshot,
+}
+
+impl Cache {
+    /// Loads the cache of the given profile from the data directory.
+    ///
+    /// Every profile has its own cache, except for the default profile which
+    /// is stored at the root of the data directory.
+    pub fn load(profile: &str) -> Self {
+        let directory = if profile == DEFAULT_PROFILE {
+            get_data_dir()
+        } else {
+            get_data_dir().join("profiles").join(profile)
+        };
+        Self::load_from(directory.join(CACHE_FILE))
+    }
+
+    /// Loads the cache from the given path.
+    ///
+    /// A missing or invalid cache file results in an empty cache.
+    pub fn load_from(path: PathBuf) -> Self {
+        let snapshot = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                log::warn!("Ignoring invalid cache at {}: {e}", path.display());
+                Snapshot::default()
+            }),
+            Err(_) => Snapshot::default(),
+        };
+        Self {
+            path,
+            snapshot: Snapshot {
+                stale: true,
+                ..snapshot
+            },
+        }
+    }
+
+    pub fn snapshot(&self) -> &Snapshot {
+        &self.snapshot
+    }
+
+    /// Reconciles the cache with the fresh state and persists it.
+    pub fn update(&mut self, fresh: PlatformState) -> Result<()> {
+        self.snapshot.state.reconcile(fresh);
+        self.snapshot.updated_at = Some(Utc::now());
+        self.snapshot.stale = false;
+        self.snapshot.offline = false;
+        self.save()
+    }
+
+    /// Marks the cached state as unreachable, which makes it read-only.
+    pub fn set_offline(&mut self) {
+        self.snapshot.offline = true;
+    }
+
+    /// Writes the snapshot to disk, without the values of the resources.
+    ///
+    /// These hold secrets, so they are fetched again instead.
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut snapshot = self.snapshot.clone();
+        for resource in snapshot.state.resources.values_mut().flatten() {
+            *resource = resource.redacted();
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&snapshot)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::shuttle::models::DeploymentState;
+
+    fn project(name: &str) -> Project {
+        Project {
+            id: String::new(),
+            name: name.to_string(),
+            state: ProjectState::Ready,
+            idle_minutes: None,
+        }
+    }
+
+    fn resource(r#type: &str) -> Resource {
+        Resource {
+            r#type: r#type.to_string(),
+            config: Default::default(),
+            data: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_reconcile() {
+        let mut state = PlatformState {
+            projects: vec![project("a"), project("b")],
+            resources: BTreeMap::from([
+                (String::from("a"), vec![resource("secrets")]),
+                (String::from("b"), vec![resource("persist")]),
+            ]),
+            ..Default::default()
+        };
+        state.reconcile(PlatformState {
+            projects: vec![project("a"), project("c")],
+            resources: BTreeMap::from([(String::from("c"), vec![resource("secrets")])]),
+            ..Default::default()
+        });
+        assert_eq!(state.projects, vec![project("a"), project("c")]);
+        assert_eq!(state.resources("a"), &[resource("secrets")]);
+        assert_eq!(state.resources("b"), &[]);
+        assert_eq!(state.resources("c"), &[resource("secrets")]);
+    }
+
+    #[test]
+    fn test_deployment_resources() {
+        let deployment = |id: &str| Deployment {
+            id: id.to_string(),
+            service_id: String::new(),
+            state: DeploymentState::Running,
+            last_update: Utc::now(),
+            git_commit_id: None,
+            git_commit_msg: None,
+            git_branch: None,
+            git_dirty: None,
+        };
+        let fetch =
+            |id: &str, deployments: Vec<Deployment>, resources: Vec<Resource>| PlatformState {
+                projects: vec![project("a")],
+                services: BTreeMap::from([(
+                    String::from("a"),
+                    Service {
+                        name: String::from("a"),
+                        deployment: Some(deployment(id)),
+                        uri: String::new(),
+                    },
+                )]),
+                deployments: BTreeMap::from([(String::from("a"), deployments)]),
+                resources: BTreeMap::from([(String::from("a"), resources)]),
+                ..Default::default()
+            };
+        let secrets = Resource {
+            data: serde_json::json!({ "API_KEY": "hunter2" }),
+            ..resource("secrets")
+        };
+        let mut state = PlatformState::default();
+        state.reconcile(fetch("1", vec![deployment("1")], vec![secrets.clone()]));
+        state.reconcile(fetch(
+            "2",
+            vec![deployment("2"), deployment("1")],
+            vec![resource("secrets"), resource("persist")],
+        ));
+        // Only the names of the secrets are kept.
+        assert_eq!(state.deployment_resources["1"], vec![secrets.redacted()]);
+        assert_eq!(
+            state.deployment_resources["1"][0].data,
+            serde_json::json!({ "API_KEY": null })
+        );
+        assert_eq!(
+            state.deployment_resources["2"],
+            vec![resource("secrets"), resource("persist")]
+        );
+        state.reconcile(fetch("2", vec![deployment("2")], vec![]));
+        assert_eq!(state.deployment_resources.get("1"), None);
+    }
+
+    #[test]
+    fn test_project_status() {
+        let deployment = |id: &str, state| Deployment {
+            id: id.to_string(),
+            service_id: String::new(),
+            state,
+            last_update: Utc::now(),
+            git_commit_id: None,
+            git_commit_msg: None,
+            git_branch: None,
+            git_dirty: None,
+        };
+        let mut state = PlatformState {
+            projects: vec![project("a"), project("b")],
+            services: BTreeMap::from([(
+                String::from("a"),
+                Service {
+                    name: String::from("a"),
+                    deployment: Some(deployment("1", DeploymentState::Running)),
+                    uri: String::new(),
+                },
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(state.project_status("a").as_deref(), Some("running"));
+        assert_eq!(state.project_status("b").as_deref(), Some("ready"));
+        assert_eq!(state.project_status("c"), None);
+        state.deployments.insert(
+            String::from("a"),
+            vec![
+                deployment("2", DeploymentState::Building),
+                deployment("1", DeploymentState::Running),
+            ],
+        );
+        assert_eq!(state.project_status("a").as_deref(), Some("building"));
+        state.projects[0].state = ProjectState::Errored(String::from("boom"));
+        assert_eq!(state.project_status("a").as_deref(), Some("errored"));
+    }
+
+    #[test]
+    fn test_secrets_are_not_persisted() -> Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("{}-secrets-{CACHE_FILE}", std::process::id()));
+        let secrets = Resource {
+            data: serde_json::json!({ "API_KEY": "hunter2" }),
+            ..resource("secrets")
+        };
+        let mut cache = Cache::load_from(path.clone());
+        cache.update(PlatformState {
+            projects: vec![project("a")],
+            resources: BTreeMap::from([(String::from("a"), vec![secrets.clone()])]),
+            ..Default::default()
+        })?;
+        assert_eq!(cache.snapshot().state.resources("a"), &[secrets]);
+        assert!(!fs::read_to_string(&path)?.contains("hunter2"));
+        let cache = Cache::load_from(path.clone());
+        assert_eq!(
+            cache.snapshot().state.resources("a")[0].data,
+            serde_json::json!({ "API_KEY": null })
+        );
+        fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_persistence() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("{}-{CACHE_FILE}", std::process::id()));
+        let mut cache = Cache::load_from(path.clone());
+        assert!(cache.snapshot().stale);
+        cache.update(PlatformState {
+            projects: vec![project("a")],
+            ..Default::default()
+        })?;
+        assert!(!cache.snapshot().stale);
+        let cache = Cache::load_from(path.clone());
+        assert!(cache.snapshot().stale);
+        assert_eq!(cache.snapshot().state.projects, vec![project("a")]);
+        fs::remove_file(path)?;
+        Ok(())
+    }
+}
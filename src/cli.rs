@@ -0,0 +1,247 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use color_eyre::eyre::{eyre, Result};
+use serde::Serialize;
+
+use crate::{
+    args::{Command, ConfigCommand, DeploymentsCommand, ProjectsCommand},
+    config::Config,
+    shuttle::{
+        models::{Deployment, DeploymentState, Project, ProjectState},
+        Shuttle,
+    },
+    utils::get_config_dir,
+};
+
+/// Interval between the requests when following the logs.
+const LOG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Runs a subcommand without entering the terminal user interface.
+pub async fn run(command: Command, shuttle: &Shuttle, json: bool) -> Result<()> {
+    match command {
+        Command::Projects {
+            command: ProjectsCommand::List,
+        } => {
+            let projects = shuttle.get_projects().await?;
+            if json {
+                return print_json(&projects);
+            }
+            print_table(
+                &["NAME", "STATE", "IDLE MINUTES"],
+                projects.iter().map(|project| {
+                    vec![
+                        project.name.clone(),
+                        project.state.to_string(),
+                        project
+                            .idle_minutes
+                            .map(|v| v.to_string())
+                            .unwrap_or_else(|| String::from("-")),
+                    ]
+                }),
+            );
+        }
+        Command::Deployments {
+            command: DeploymentsCommand::List { project },
+        } => {
+            let deployments = shuttle.get_deployments(&project).await?;
+            if json {
+                return print_json(&deployments);
+            }
+            print_table(
+                &["ID", "STATE", "LAST UPDATE", "BRANCH", "COMMIT"],
+                deployments.iter().map(|deployment| {
+                    vec![
+                        deployment.id.clone(),
+                        deployment.state.to_string(),
+                        deployment.last_update.to_rfc3339(),
+                        deployment.git_branch.clone().unwrap_or_default(),
+                        deployment
+                            .git_commit_msg
+                            .as_deref()
+                            .and_then(|v| v.lines().next())
+                            .unwrap_or_default()
+                            .to_string(),
+                    ]
+                }),
+            );
+        }
+        Command::Logs {
+            project,
+            id,
+            follow,
+        } => print_logs(shuttle, &project, id, follow, json).await?,
+        Command::Status => print_status(shuttle, json).await?,
+        Command::Config {
+            command: ConfigCommand::Check,
+        } => check_config(json)?,
+    }
+    Ok(())
+}
+
+async fn print_logs(
+    shuttle: &Shuttle,
+    project: &str,
+    id: Option<String>,
+    follow: bool,
+    json: bool,
+) -> Result<()> {
+    let id = match id {
+        Some(id) => id,
+        None => shuttle
+            .get_deployments(project)
+            .await?
+            .into_iter()
+            .max_by_key(|deployment| deployment.last_update)
+            .map(|deployment| deployment.id)
+            .ok_or_else(|| eyre!("No deployments found for {project}"))?,
+    };
+    let mut printed = 0;
+    loop {
+        let logs = shuttle.get_logs(project, &id).await?;
+        for log in logs.iter().skip(printed) {
+            if json {
+                println!("{}", serde_json::to_string(log)?);
+            } else {
+                println!(
+                    "{} [{}] {}",
+                    log.timestamp.to_rfc3339(),
+                    log.internal_origin,
+                    log.line
+                );
+            }
+        }
+        printed = printed.max(logs.len());
+        if !follow {
+            return Ok(());
+        }
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            _ = tokio::time::sleep(LOG_POLL_INTERVAL) => {}
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ProjectStatus {
+    name: String,
+    state: ProjectState,
+    uri: Option<String>,
+    deployment: Option<Deployment>,
+}
+
+#[derive(Serialize)]
+struct Status {
+    api_url: String,
+    authenticated: bool,
+    projects: Vec<ProjectStatus>,
+}
+
+async fn print_status(shuttle: &Shuttle, json: bool) -> Result<()> {
+    let mut status = Status {
+        api_url: shuttle.api_url().to_string(),
+        authenticated: shuttle.is_authenticated(),
+        projects: Vec::new(),
+    };
+    for Project { name, state, .. } in shuttle.get_projects().await? {
+        let service = shuttle.get_service(&name).await.ok();
+        status.projects.push(ProjectStatus {
+            uri: service.as_ref().map(|v| v.uri.clone()),
+            deployment: service.and_then(|v| v.deployment),
+            name,
+            state,
+        });
+    }
+    if json {
+        return print_json(&status);
+    }
+    println!("API: {}", status.api_url);
+    println!(
+        "Authenticated: {}",
+        if status.authenticated { "yes" } else { "no" }
+    );
+    println!();
+    print_table(
+        &["PROJECT", "STATE", "DEPLOYMENT", "URI"],
+        status.projects.iter().map(|project| {
+            vec![
+                project.name.clone(),
+                project.state.to_string(),
+                project
+                    .deployment
+                    .as_ref()
+                    .map(|v| format!("{} ({})", v.short_id(), v.state))
+                    .unwrap_or_else(|| String::from("-")),
+                project.uri.clone().unwrap_or_else(|| String::from("-")),
+            ]
+        }),
+    );
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct ConfigReport {
+    config_dir: String,
+    files: Vec<String>,
+    keybindings: BTreeMap<String, usize>,
+}
+
+fn check_config(json: bool) -> Result<()> {
+    let config = Config::new()?;
+    let report = ConfigReport {
+        config_dir: get_config_dir().display().to_string(),
+        files: Config::files()
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect(),
+        keybindings: config
+            .keybindings
+            .iter()
+            .map(|(scope, bindings)| (scope.to_string(), bindings.len()))
+            .collect(),
+    };
+    if json {
+        return print_json(&report);
+    }
+    println!("Config directory: {}", report.config_dir);
+    if report.files.is_empty() {
+        println!("No configuration file found, using the defaults");
+    }
+    for file in &report.files {
+        println!("Loaded: {file}");
+    }
+    for (scope, count) in &report.keybindings {
+        println!("{scope}: {count} key bindings");
+    }
+    println!("Configuration is valid");
+    Ok(())
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Prints the rows as a table with aligned columns.
+fn print_table(headers: &[&str], rows: impl Iterator<Item = Vec<String>>) {
+    let rows = rows.collect::<Vec<_>>();
+    let mut widths = headers.iter().map(|v| v.len()).collect::<Vec<_>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    println!("{}", format_row(headers.to_vec()));
+    for row in &rows {
+        println!("{}", format_row(row.iter().map(String::as_str).collect()));
+    }
+}
@@ -1,14 +1,32 @@
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyEvent, MouseEvent};
-use ratatui::{layout::Rect, Frame};
+use ratatui::{prelude::*, widgets::*};
 use tokio::sync::mpsc::UnboundedSender;
 
-use crate::{action::Action, config::Config, tab::Tab, tui::Event};
+use crate::{action::Action, config::Config, tab::Tab, task::TaskManager, tui::Event};
 
+pub mod command_output;
+pub mod dashboard;
+pub mod deployment_details;
+pub mod deployment_diff;
 pub mod deployments;
+pub mod domains;
+pub mod editor;
+pub mod form;
 pub mod home;
+pub mod init;
+pub mod local;
+pub mod logs;
+pub mod new_project;
+pub mod notifications;
+pub mod profiles;
 pub mod projects;
+pub mod request_runner;
+pub mod resource_graph;
+pub mod sql_console;
 pub mod tab;
+pub mod tasks;
+pub mod terminal;
 
 //// ANCHOR: component
 pub trait Component {
@@ -26,6 +44,29 @@ pub trait Component {
     fn register_config_handler(&mut self, config: Config) -> Result<()> {
         Ok(())
     }
+    #[allow(unused_variables)]
+    fn register_task_handler(&mut self, tasks: TaskManager) -> Result<()> {
+        Ok(())
+    }
+    /// Returns `true` if the component can take the keyboard focus.
+    fn focusable(&self) -> bool {
+        false
+    }
+    /// Called when the component gains or loses the keyboard focus.
+    #[allow(unused_variables)]
+    fn set_focused(&mut self, focused: bool) {}
+    /// Returns the name of the key binding scope of the component.
+    ///
+    /// While the component is focused, the bindings of its scope take
+    /// precedence over the ones of the pane and the global ones.
+    fn key_scope(&self) -> Option<&'static str> {
+        None
+    }
+    /// Returns `true` if the component takes all key events while focused,
+    /// bypassing the key bindings.
+    fn captures_input(&self) -> bool {
+        false
+    }
     fn handle_events(&mut self, event: Option<Event>) -> Result<Option<Action>> {
         let r = match event {
             Some(Event::Key(key_event)) => self.handle_key_events(key_event)?,
@@ -49,3 +90,24 @@ pub trait Component {
     fn draw(&mut self, f: &mut Frame, rect: Rect) -> Result<()>;
 }
 //// ANCHOR_END: component
+
+/// Returns a rectangle of the given size that is centered in the area.
+pub fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Returns a rounded block with a bold title, as used by the full-screen pages.
+pub fn titled_block(title: &str) -> Block<'_> {
+    Block::default()
+        .title(Line::from(title.bold()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+}
@@ -0,0 +1,105 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Component, Frame};
+use crate::{action::Action, ansi, config::Config};
+
+/// Popup with the output of a command of the configuration.
+#[derive(Default)]
+pub struct CommandOutput {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// Name of the command and its output.
+    output: Option<(String, Vec<Line<'static>>)>,
+    scroll: usize,
+}
+
+impl CommandOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Component for CommandOutput {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.output.is_some()
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("Modal")
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let Some((_, lines)) = &self.output else {
+            return Ok(None);
+        };
+        let max = lines.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter => return Ok(Some(Action::CloseOutput)),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = (self.scroll + 1).min(max),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Char('g') | KeyCode::Home => self.scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => self.scroll = max,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowOutput(name, output) => {
+                let lines = output.lines().map(ansi::parse_line).collect();
+                self.output = Some((name, lines));
+                self.scroll = 0;
+            }
+            Action::CloseOutput => self.output = None,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let Some((name, lines)) = &self.output else {
+            return Ok(());
+        };
+        let rect = area.inner(&Margin {
+            horizontal: 6,
+            vertical: 3,
+        });
+        f.render_widget(Clear, rect);
+        let paragraph = if lines.is_empty() {
+            Paragraph::new(Line::from("The command printed nothing".italic()))
+        } else {
+            Paragraph::new(lines.clone()).scroll((self.scroll as u16, 0))
+        };
+        f.render_widget(
+            paragraph.block(
+                Block::default()
+                    .title(Line::from(format!("Output of {name}").bold()))
+                    .title(
+                        block::Title::from("j/k: scroll · esc: close")
+                            .position(block::Position::Bottom)
+                            .alignment(Alignment::Right),
+                    )
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .padding(Padding::horizontal(1)),
+            ),
+            rect,
+        );
+        Ok(())
+    }
+}
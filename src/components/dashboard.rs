@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Component, Frame};
+use crate::{
+    action::Action,
+    cache::Snapshot,
+    config::Config,
+    probe::{self, ServiceHealth, ERROR_WINDOW_MINUTES},
+    shuttle::models::Deployment,
+    tab::Tab,
+    utils::humanize_duration,
+};
+
+/// Smallest width of a card, which decides the number of columns.
+const CARD_WIDTH: u16 = 44;
+
+const CARD_HEIGHT: u16 = 10;
+
+/// Tab with a card of the health of each running service.
+#[derive(Default)]
+pub struct Dashboard {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    snapshot: Snapshot,
+    /// Probes by project.
+    health: HashMap<String, ServiceHealth>,
+    selected: usize,
+    /// Number of cards per row at the last draw.
+    columns: usize,
+}
+
+impl Dashboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the running services along with their current deployment.
+    fn services(&self) -> Vec<(&str, &Deployment)> {
+        probe::targets(&self.snapshot.state)
+            .into_iter()
+            .filter_map(|(project, _, _)| {
+                let (project, service) = self.snapshot.state.services.get_key_value(&project)?;
+                Some((project.as_str(), service.deployment.as_ref()?))
+            })
+            .collect()
+    }
+
+    fn select(&mut self, offset: isize) {
+        let len = self.services().len() as isize;
+        self.selected = (self.selected as isize + offset).clamp(0, (len - 1).max(0)) as usize;
+    }
+
+    fn card(
+        &self,
+        f: &mut Frame<'_>,
+        area: Rect,
+        project: &str,
+        deployment: &Deployment,
+        selected: bool,
+    ) {
+        let health = self.health.get(project);
+        let last = health.and_then(ServiceHealth::last);
+        let color = match last {
+            Some(check) if check.is_healthy() => Color::Green,
+            Some(_) => Color::Red,
+            None => Color::DarkGray,
+        };
+        let mut block = Block::default()
+            .title(Line::from(vec![
+                Span::styled("● ", Style::default().fg(color)),
+                Span::from(project.to_string()).bold(),
+            ]))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::horizontal(1));
+        if selected {
+            block = block.border_style(Style::default().fg(Color::Rgb(253, 145, 62)));
+        }
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(4), Constraint::Min(1)])
+            .split(inner);
+
+        let label = |name: &'static str| Span::styled(name, Style::default().fg(Color::DarkGray));
+        let running_for = humanize_duration(Utc::now() - deployment.last_update);
+        let uptime = match health.and_then(ServiceHealth::uptime) {
+            Some(uptime) => {
+                let color = match uptime {
+                    v if v >= 0.99 => Color::Green,
+                    v if v >= 0.9 => Color::Yellow,
+                    _ => Color::Red,
+                };
+                Span::styled(
+                    format!(
+                        "{:.1}% of {} probes",
+                        uptime * 100.0,
+                        health.map_or(0, |v| v.checks.len())
+                    ),
+                    Style::default().fg(color),
+                )
+            }
+            None => Span::raw("no probes yet"),
+        };
+        let errors = match health.and_then(|v| v.errors) {
+            Some(0) => Span::styled("none", Style::default().fg(Color::Green)),
+            Some(errors) => Span::styled(errors.to_string(), Style::default().fg(Color::Red)),
+            None => Span::raw("-"),
+        };
+        let latency = match last {
+            Some(check) if check.status.is_some() => format!(
+                "{}ms, {}ms on average",
+                check.latency_ms,
+                health
+                    .and_then(ServiceHealth::average_latency)
+                    .unwrap_or_default()
+            ),
+            Some(check) => check.error.clone().unwrap_or_else(|| "failed".into()),
+            None => String::from("-"),
+        };
+        let lines = vec![
+            Line::from(vec![
+                label("Deployment "),
+                Span::raw(format!("{} · up {running_for}", deployment.short_id())),
+            ]),
+            Line::from(vec![label("Uptime     "), uptime]),
+            Line::from(vec![
+                label("Errors     "),
+                errors,
+                Span::styled(
+                    format!(" in {ERROR_WINDOW_MINUTES}m"),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            Line::from(vec![label("Latency    "), Span::raw(latency)]),
+        ];
+        f.render_widget(Paragraph::new(lines), rows[0]);
+
+        let latencies = health.map(ServiceHealth::latencies).unwrap_or_default();
+        // The most recent probes are kept when the card is too narrow for all of them.
+        let skip = latencies.len().saturating_sub(rows[1].width as usize);
+        f.render_widget(
+            Sparkline::default()
+                .data(&latencies[skip..])
+                .style(Style::default().fg(color)),
+            rows[1],
+        );
+    }
+}
+
+impl Component for Dashboard {
+    fn assigned_tab(&self) -> Option<Tab> {
+        Some(Tab::Dashboard)
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let columns = self.columns.max(1) as isize;
+        let action = match key.code {
+            KeyCode::Right => {
+                self.select(1);
+                None
+            }
+            KeyCode::Left => {
+                self.select(-1);
+                None
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.select(columns);
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.select(-columns);
+                None
+            }
+            KeyCode::Enter => self
+                .services()
+                .get(self.selected)
+                .map(|(project, deployment)| {
+                    Action::OpenLogs(project.to_string(), deployment.id.clone())
+                }),
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::PlatformUpdated(snapshot) => {
+                self.snapshot = *snapshot;
+                let services = self
+                    .services()
+                    .into_iter()
+                    .map(|(project, _)| project.to_string())
+                    .collect::<Vec<_>>();
+                self.health.retain(|project, _| services.contains(project));
+                self.select(0);
+            }
+            Action::ServiceProbed(probe) => {
+                let capacity = self.config.dashboard.history;
+                self.health
+                    .entry(probe.project.clone())
+                    .or_default()
+                    .record(*probe, capacity);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let services = self.services();
+        if services.is_empty() {
+            let message = if self.config.dashboard.probe_interval == 0 {
+                "No running services, and the probes are disabled"
+            } else {
+                "No running services"
+            };
+            f.render_widget(
+                Paragraph::new(message.italic())
+                    .alignment(Alignment::Center)
+                    .block(Block::default().padding(Padding::vertical(1))),
+                area,
+            );
+            return Ok(());
+        }
+        let columns = (area.width / CARD_WIDTH).max(1) as usize;
+        let visible_rows = (area.height / CARD_HEIGHT).max(1) as usize;
+        // Scrolls by whole rows to keep the selected card visible.
+        let first_row = (self.selected / columns).saturating_sub(visible_rows - 1);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Length(CARD_HEIGHT); visible_rows])
+            .split(area);
+        let cards = services
+            .iter()
+            .enumerate()
+            .skip(first_row * columns)
+            .take(visible_rows * columns)
+            .collect::<Vec<_>>();
+        for (row, chunk) in rows.iter().zip(cards.chunks(columns)) {
+            let cells = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+                .split(*row);
+            for (cell, (index, (project, deployment))) in cells.iter().zip(chunk) {
+                self.card(f, *cell, project, deployment, *index == self.selected);
+            }
+        }
+        self.columns = columns;
+        Ok(())
+    }
+}
@@ -0,0 +1,296 @@
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{deployments::deployment_state_color, titled_block, Component, Frame};
+use crate::{
+    action::Action,
+    cache::Snapshot,
+    config::Config,
+    shuttle::models::{Deployment, DeploymentDetails, StateTransition},
+    tab::Tab,
+    utils::humanize_duration,
+};
+
+/// Full-screen page that shows the details of a deployment.
+#[derive(Default)]
+pub struct DeploymentDetailsPage {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    snapshot: Snapshot,
+    /// Project and ID of the open deployment.
+    open: Option<(String, String)>,
+    details: Option<DeploymentDetails>,
+}
+
+impl DeploymentDetailsPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn is_open(&self, details: &DeploymentDetails) -> bool {
+        self.open.as_ref().is_some_and(|(project, id)| {
+            project == &details.project && id == &details.deployment_id
+        })
+    }
+
+    fn deployment(&self) -> Option<&Deployment> {
+        let (project, id) = self.open.as_ref()?;
+        self.snapshot
+            .state
+            .deployments(project)
+            .iter()
+            .find(|deployment| &deployment.id == id)
+    }
+
+    /// Returns the known state transitions of the deployment.
+    ///
+    /// If the transitions could not be extracted from the logs, the current
+    /// state is shown as the only entry.
+    fn transitions(&self, deployment: &Deployment) -> Vec<StateTransition> {
+        match &self.details {
+            Some(details) if !details.transitions.is_empty() => details.transitions.clone(),
+            _ => vec![StateTransition {
+                state: deployment.state,
+                timestamp: deployment.last_update,
+            }],
+        }
+    }
+
+    fn timeline(&self, deployment: &Deployment) -> Table<'static> {
+        let transitions = self.transitions(deployment);
+        let rows = transitions
+            .iter()
+            .enumerate()
+            .map(|(i, transition)| {
+                let end = match transitions.get(i + 1) {
+                    Some(next) => Some(next.timestamp),
+                    // The current state is still ongoing.
+                    None if transition.state == deployment.state => Some(Utc::now()),
+                    None => None,
+                };
+                let duration = end
+                    .map(|end| humanize_duration(end - transition.timestamp))
+                    .unwrap_or_else(|| String::from("-"));
+                Row::new(vec![
+                    Cell::from(if i == 0 { "  " } else { "→ " }),
+                    Cell::from(transition.state.to_string())
+                        .style(Style::default().fg(deployment_state_color(transition.state))),
+                    Cell::from(format_timestamp(transition.timestamp)),
+                    Cell::from(duration),
+                ])
+            })
+            .collect::<Vec<Row>>();
+        Table::new(rows)
+            .header(
+                Row::new(vec!["", "State", "Entered at", "Duration"])
+                    .bottom_margin(1)
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(titled_block("Timeline"))
+            .widths(&[
+                Constraint::Length(2),
+                Constraint::Length(10),
+                Constraint::Length(20),
+                Constraint::Length(10),
+            ])
+    }
+
+    fn build_info(&self, deployment: &Deployment) -> Paragraph<'static> {
+        let field = |name: &str, value: Option<String>| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{name:<8}"),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(value.unwrap_or_else(|| String::from("-"))),
+            ])
+        };
+        let author = self.details.as_ref().and_then(|v| v.author.clone());
+        let mut lines = vec![
+            field("Commit", deployment.git_commit_id.clone()),
+            field("Branch", deployment.git_branch.clone()),
+            field("Author", author),
+            field(
+                "Dirty",
+                deployment
+                    .git_dirty
+                    .map(|dirty| String::from(if dirty { "yes" } else { "no" })),
+            ),
+            Line::default(),
+        ];
+        lines.extend(
+            deployment
+                .git_commit_msg
+                .clone()
+                .unwrap_or_default()
+                .lines()
+                .map(|line| Line::from(line.to_string().italic())),
+        );
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(titled_block("Build"))
+    }
+
+    fn service_info(&self, project: &str, deployment: &Deployment) -> Paragraph<'static> {
+        let mut lines = Vec::new();
+        match self.snapshot.state.services.get(project) {
+            Some(service) => lines.push(Line::from(vec![
+                "URI     ".bold(),
+                Span::raw(service.uri.clone()),
+            ])),
+            None => lines.push(Line::from("No service found".italic())),
+        }
+        match self.details.as_ref().and_then(|v| v.health_check.as_ref()) {
+            Some(check) => {
+                let (status, color) = match (check.status, &check.error) {
+                    (Some(status), _) if check.is_healthy() => (status.to_string(), Color::Green),
+                    (Some(status), _) => (status.to_string(), Color::Red),
+                    (None, error) => (error.clone().unwrap_or_default(), Color::Red),
+                };
+                lines.push(Line::from(vec![
+                    "Health  ".bold(),
+                    Span::raw(format!("GET {} → ", check.endpoint)),
+                    Span::styled(status, Style::default().fg(color)),
+                    Span::raw(format!(
+                        " in {}ms at {}",
+                        check.latency_ms,
+                        format_timestamp(check.checked_at)
+                    )),
+                ]));
+            }
+            None if self.details.is_none() => {
+                lines.push(Line::from(vec!["Health  ".bold(), "checking…".italic()]))
+            }
+            None => lines.push(Line::from(vec!["Health  ".bold(), Span::raw("-")])),
+        }
+        lines.push(Line::default());
+        // Only deployments that were running during a refresh have resources
+        // of their own, the others fall back to the current ones of the project.
+        let (title, resources) = match self.snapshot.state.deployment_resources.get(&deployment.id)
+        {
+            Some(resources) => ("Resources", resources.as_slice()),
+            None => (
+                "Resources of the project",
+                self.snapshot.state.resources(project),
+            ),
+        };
+        lines.push(Line::from(title.bold()));
+        if resources.is_empty() {
+            lines.push(Line::from("No resources".italic()));
+        }
+        for resource in resources {
+            lines.push(Line::from(format!(" • {}", resource.r#type)));
+        }
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(titled_block("Service"))
+    }
+}
+
+pub fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+impl Component for DeploymentDetailsPage {
+    fn assigned_tab(&self) -> Option<Tab> {
+        Some(Tab::Deployments)
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.open.is_some()
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("DeploymentDetails")
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.open.is_some() && key.code == KeyCode::Esc {
+            return Ok(Some(Action::CloseDeployment));
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::PlatformUpdated(snapshot) => self.snapshot = *snapshot,
+            Action::OpenDeployment(project, id) => {
+                self.open = Some((project, id));
+                self.details = None;
+            }
+            Action::CloseDeployment => {
+                self.open = None;
+                self.details = None;
+            }
+            Action::DeploymentDetailsFetched(details) if self.is_open(&details) => {
+                self.details = Some(*details);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let Some((project, id)) = self.open.clone() else {
+            return Ok(());
+        };
+        f.render_widget(Clear, area);
+        let Some(deployment) = self.deployment().cloned() else {
+            f.render_widget(
+                Paragraph::new(format!("Deployment {id} of {project} is not known"))
+                    .block(titled_block("Deployment")),
+                area,
+            );
+            return Ok(());
+        };
+        let rect = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("{project} · {} ", deployment.id),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    deployment.state.to_string(),
+                    Style::default().fg(deployment_state_color(deployment.state)),
+                ),
+            ]))
+            .block(
+                titled_block("Deployment").title(
+                    block::Title::from("esc: back")
+                        .alignment(Alignment::Right)
+                        .position(block::Position::Top),
+                ),
+            ),
+            rect[0],
+        );
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(rect[1]);
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(columns[1]);
+        f.render_widget(self.timeline(&deployment), columns[0]);
+        f.render_widget(self.build_info(&deployment), right[0]);
+        f.render_widget(self.service_info(&project, &deployment), right[1]);
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{
+    deployment_details::format_timestamp, deployments::deployment_state_color, titled_block,
+    Component, Frame,
+};
+use crate::{
+    action::Action,
+    config::Config,
+    diff::{Change, DeploymentDiff, DiffLine, MANIFEST_FILE},
+    shuttle::models::Deployment,
+    tab::Tab,
+};
+
+/// Full-screen page that compares two deployments side by side.
+#[derive(Default)]
+pub struct DeploymentDiffPane {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// Project and IDs of the compared deployments.
+    open: Option<(String, String, String)>,
+    diff: Option<DeploymentDiff>,
+    scroll: u16,
+}
+
+impl DeploymentDiffPane {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn is_open(&self, diff: &DeploymentDiff) -> bool {
+        self.open.as_ref().is_some_and(|(project, a, b)| {
+            let ids = [&diff.from.id, &diff.to.id];
+            project == &diff.project && ids.contains(&a) && ids.contains(&b)
+        })
+    }
+
+    fn summary(diff: &DeploymentDiff) -> Table<'static> {
+        let fields = |v: &Deployment| {
+            [
+                v.id.clone(),
+                v.state.to_string(),
+                format_timestamp(v.last_update),
+                v.git_branch.clone().unwrap_or_default(),
+                v.git_commit_id.clone().unwrap_or_default(),
+                match v.git_dirty {
+                    Some(true) => String::from("yes"),
+                    Some(false) => String::from("no"),
+                    None => String::from("-"),
+                },
+            ]
+        };
+        let names = ["ID", "State", "Updated", "Branch", "Commit", "Dirty"];
+        let rows = names
+            .into_iter()
+            .zip(fields(&diff.from).into_iter().zip(fields(&diff.to)))
+            .map(|(name, (old, new))| {
+                let style = if name == "State" {
+                    Style::default().fg(deployment_state_color(diff.to.state))
+                } else if old != new {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                let old_style = if name == "State" {
+                    Style::default().fg(deployment_state_color(diff.from.state))
+                } else {
+                    Style::default()
+                };
+                Row::new(vec![
+                    Cell::from(name).style(Style::default().add_modifier(Modifier::BOLD)),
+                    Cell::from(old).style(old_style),
+                    Cell::from(new).style(style),
+                ])
+            })
+            .collect::<Vec<Row>>();
+        Table::new(rows)
+            .header(
+                Row::new(vec!["", "Before", "After"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .widths(&[
+                Constraint::Length(8),
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
+            ])
+    }
+
+    fn commits(diff: &DeploymentDiff) -> Paragraph<'static> {
+        let lines = match &diff.commits {
+            Some(commits) if commits.is_empty() => vec![Line::from("No new commits".italic())],
+            Some(commits) => commits
+                .iter()
+                .map(|commit| Line::from(commit.clone()))
+                .collect(),
+            None => vec![Line::from("Commits are not available locally".italic())],
+        };
+        Paragraph::new(lines).block(titled_block("Commits"))
+    }
+
+    fn changes(title: &'static str, changes: &Option<Vec<Change>>) -> Paragraph<'static> {
+        let lines = match changes {
+            Some(changes) if changes.is_empty() => vec![Line::from("None".italic())],
+            Some(changes) => changes
+                .iter()
+                .map(|change| match change {
+                    Change::Added(v) => {
+                        Line::styled(format!("+ {v}"), Style::default().fg(Color::Green))
+                    }
+                    Change::Removed(v) => {
+                        Line::styled(format!("- {v}"), Style::default().fg(Color::Red))
+                    }
+                    Change::Unchanged(v) => Line::from(format!("  {v}")),
+                })
+                .collect(),
+            None => vec![Line::from("Unknown for older deployments".italic())],
+        };
+        Paragraph::new(lines).block(titled_block(title))
+    }
+
+    fn manifest(&self, diff: &DeploymentDiff) -> [Paragraph<'static>; 2] {
+        let Some(manifest) = &diff.manifest else {
+            let message = format!("{MANIFEST_FILE} is not available locally");
+            return [
+                Paragraph::new(Line::from(message.clone().italic())).block(titled_block("Before")),
+                Paragraph::new(Line::from(message.italic())).block(titled_block("After")),
+            ];
+        };
+        let line = |line: &DiffLine| match line {
+            DiffLine::Unchanged(v) => Line::from(format!("  {v}")),
+            DiffLine::Removed(v) => Line::styled(format!("- {v}"), Style::default().fg(Color::Red)),
+            DiffLine::Added(v) => Line::styled(format!("+ {v}"), Style::default().fg(Color::Green)),
+            DiffLine::Empty => Line::default(),
+        };
+        let (old, new): (Vec<Line>, Vec<Line>) = manifest
+            .iter()
+            .map(|(old, new)| (line(old), line(new)))
+            .unzip();
+        [
+            Paragraph::new(old)
+                .scroll((self.scroll, 0))
+                .block(titled_block("Before")),
+            Paragraph::new(new)
+                .scroll((self.scroll, 0))
+                .block(titled_block("After")),
+        ]
+    }
+}
+
+impl Component for DeploymentDiffPane {
+    fn assigned_tab(&self) -> Option<Tab> {
+        Some(Tab::Deployments)
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.open.is_some()
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("DeploymentDiff")
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.open.is_none() {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Esc => return Ok(Some(Action::CloseComparison)),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::CompareDeployments(project, from, to) => {
+                self.open = Some((project, from, to));
+                self.diff = None;
+                self.scroll = 0;
+            }
+            Action::CloseComparison => {
+                self.open = None;
+                self.diff = None;
+            }
+            Action::DeploymentsCompared(diff) if self.is_open(&diff) => self.diff = Some(*diff),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let Some((project, ..)) = &self.open else {
+            return Ok(());
+        };
+        f.render_widget(Clear, area);
+        let block = titled_block("Compare deployments").title(
+            block::Title::from("j/k: scroll · esc: back")
+                .alignment(Alignment::Right)
+                .position(block::Position::Top),
+        );
+        let Some(diff) = &self.diff else {
+            f.render_widget(
+                Paragraph::new(format!("Comparing the deployments of {project}…")).block(block),
+                area,
+            );
+            return Ok(());
+        };
+        let rect = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(9),
+                Constraint::Length(8),
+                Constraint::Min(0),
+            ])
+            .split(area);
+        f.render_widget(Self::summary(diff).block(block), rect[0]);
+        let changes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(50),
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+            ])
+            .split(rect[1]);
+        f.render_widget(Self::commits(diff), changes[0]);
+        f.render_widget(Self::changes("Resources", &diff.resources), changes[1]);
+        f.render_widget(Self::changes("Secrets", &diff.secrets), changes[2]);
+        let manifest = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(rect[2]);
+        let [old, new] = self.manifest(diff);
+        f.render_widget(old, manifest[0]);
+        f.render_widget(new, manifest[1]);
+        Ok(())
+    }
+}
@@ -6,10 +6,12 @@ use ratatui::{prelude::*, widgets::*};
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc::UnboundedSender;
 
-use super::{Component, Frame};
+use super::{centered_rect, projects::snapshot_title, Component, Frame};
 use crate::{
     action::Action,
+    cache::Snapshot,
     config::{Config, KeyBindings},
+    shuttle::models::{Deployment, DeploymentState},
     tab::Tab,
 };
 
@@ -17,12 +19,123 @@ use crate::{
 pub struct Deployments {
     command_tx: Option<UnboundedSender<Action>>,
     config: Config,
+    snapshot: Snapshot,
+    project: Option<String>,
+    state: TableState,
+    /// ID of the deployment that is marked for comparison.
+    marked: Option<String>,
+    /// ID of the deployment that is waiting for the confirmation of a redeploy.
+    confirm_redeploy: Option<String>,
+    show_overlay: bool,
+    focused: bool,
 }
 
 impl Deployments {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn deployments(&self) -> &[Deployment] {
+        self.project
+            .as_ref()
+            .map(|project| self.snapshot.state.deployments(project))
+            .unwrap_or_default()
+    }
+
+    fn selected_deployment(&self) -> Option<&Deployment> {
+        self.state
+            .selected()
+            .and_then(|index| self.deployments().get(index))
+    }
+
+    /// Returns the deployment that is currently serving the project.
+    fn current_deployment(&self) -> Option<&Deployment> {
+        let project = self.project.as_ref()?;
+        let service = self.snapshot.state.services.get(project);
+        service
+            .and_then(|service| service.deployment.as_ref())
+            .or_else(|| {
+                self.deployments()
+                    .iter()
+                    .find(|deployment| deployment.state == DeploymentState::Running)
+            })
+    }
+
+    fn handle_confirmation(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                let id = self.confirm_redeploy.take()?;
+                self.project
+                    .clone()
+                    .map(|project| Action::Redeploy(project, id))
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.confirm_redeploy = None;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn draw_confirmation(&self, f: &mut Frame<'_>, area: Rect, id: &str) {
+        let summary = |deployment: &Deployment| {
+            let message = deployment
+                .git_commit_msg
+                .as_deref()
+                .and_then(|v| v.lines().next())
+                .unwrap_or("no commit message");
+            format!("{} ({}) {message}", deployment.short_id(), deployment.state)
+        };
+        let Some(deployment) = self.deployments().iter().find(|v| v.id == id) else {
+            return;
+        };
+        let current = self
+            .current_deployment()
+            .map(summary)
+            .unwrap_or_else(|| String::from("none"));
+        let rect = centered_rect(area, 70, 10);
+        f.render_widget(Clear, rect);
+        f.render_widget(
+            Paragraph::new(vec![
+                Line::from(vec!["Build:    ".bold(), Span::raw(summary(deployment))]),
+                Line::from(vec!["Replaces: ".bold(), Span::raw(current)]),
+                Line::default(),
+                Line::from("Redeploy this build? (y/n)".bold()),
+            ])
+            .block(
+                Block::default()
+                    .title("Redeploy")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .padding(Padding::uniform(1)),
+            ),
+            rect,
+        );
+    }
+
+    fn select(&mut self, offset: isize) {
+        let len = self.deployments().len();
+        if len == 0 {
+            self.state.select(None);
+            return;
+        }
+        let current = self.state.selected().unwrap_or_default() as isize;
+        let index = (current + offset).clamp(0, len as isize - 1) as usize;
+        self.state.select(Some(index));
+    }
+}
+
+pub fn deployment_state_color(state: DeploymentState) -> Color {
+    match state {
+        DeploymentState::Running => Color::Green,
+        DeploymentState::Crashed => Color::Red,
+        DeploymentState::Completed | DeploymentState::Stopped | DeploymentState::Unknown => {
+            Color::DarkGray
+        }
+        _ => Color::Yellow,
+    }
 }
 
 impl Component for Deployments {
@@ -39,16 +152,163 @@ impl Component for Deployments {
         Ok(())
     }
 
+    fn focusable(&self) -> bool {
+        !self.show_overlay
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        self.confirm_redeploy.as_ref().map(|_| "Modal")
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.confirm_redeploy.is_some() {
+            return Ok(self.handle_confirmation(key));
+        }
+        let action = match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.select(1);
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.select(-1);
+                None
+            }
+            KeyCode::Enter => self.project.clone().and_then(|project| {
+                self.selected_deployment()
+                    .map(|deployment| Action::OpenDeployment(project, deployment.id.clone()))
+            }),
+            KeyCode::Char('L') => self.project.clone().and_then(|project| {
+                self.selected_deployment()
+                    .map(|deployment| Action::OpenLogs(project, deployment.id.clone()))
+            }),
+            KeyCode::Char('R') => {
+                match self.selected_deployment() {
+                    Some(deployment) if self.snapshot.is_read_only() => {
+                        log::warn!("Cannot redeploy {} while offline", deployment.id);
+                    }
+                    Some(deployment) if deployment.state.is_redeployable() => {
+                        self.confirm_redeploy = Some(deployment.id.clone());
+                    }
+                    _ => {}
+                }
+                None
+            }
+            KeyCode::Char('m') => {
+                let id = self.selected_deployment().map(|v| v.id.clone());
+                self.marked = if self.marked == id { None } else { id };
+                None
+            }
+            KeyCode::Char('c') => {
+                let selected = self.selected_deployment().map(|v| v.id.clone());
+                match (self.project.clone(), self.marked.clone(), selected) {
+                    (Some(project), Some(marked), Some(selected)) if marked != selected => {
+                        Some(Action::CompareDeployments(project, marked, selected))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
-            Action::Tick => {}
+            Action::PlatformUpdated(snapshot) => {
+                self.snapshot = *snapshot;
+                self.select(0);
+            }
+            Action::SelectProject(name) if self.project.as_ref() != Some(&name) => {
+                self.project = Some(name);
+                self.marked = None;
+                self.confirm_redeploy = None;
+                self.state.select(None);
+                self.select(0);
+            }
+            Action::OpenDeployment(..) | Action::CompareDeployments(..) => self.show_overlay = true,
+            Action::CloseDeployment | Action::CloseComparison => self.show_overlay = false,
             _ => {}
         }
         Ok(None)
     }
 
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
-        f.render_widget(Paragraph::new("deployments"), area);
+        let rows = self
+            .deployments()
+            .iter()
+            .map(|deployment| {
+                let id = if self.marked.as_ref() == Some(&deployment.id) {
+                    Cell::from(format!("{}*", deployment.short_id()))
+                        .style(Style::default().add_modifier(Modifier::BOLD))
+                } else {
+                    Cell::from(deployment.short_id().to_string())
+                };
+                Row::new(vec![
+                    id,
+                    Cell::from(deployment.state.to_string())
+                        .style(Style::default().fg(deployment_state_color(deployment.state))),
+                    Cell::from(
+                        deployment
+                            .last_update
+                            .format("%Y-%m-%d %H:%M:%S")
+                            .to_string(),
+                    ),
+                    Cell::from(deployment.git_branch.clone().unwrap_or_default()),
+                    Cell::from(
+                        deployment
+                            .git_commit_msg
+                            .as_deref()
+                            .and_then(|v| v.lines().next())
+                            .unwrap_or_default()
+                            .to_string(),
+                    ),
+                ])
+            })
+            .collect::<Vec<Row>>();
+        let title = match &self.project {
+            Some(project) => format!("Deployments of {project}"),
+            None => String::from("Deployments"),
+        };
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec!["ID", "State", "Last update", "Branch", "Commit"])
+                    .bottom_margin(1)
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(
+                Block::default()
+                    .title(snapshot_title(&title, &self.snapshot))
+                    .title(
+                        block::Title::from(
+                            "enter: details · L: logs · R: redeploy · m: mark · c: compare",
+                        )
+                        .alignment(Alignment::Right)
+                        .position(block::Position::Bottom),
+                    )
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            )
+            .widths(&[
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(20),
+                Constraint::Length(16),
+                Constraint::Percentage(100),
+            ])
+            .highlight_style(Style::default().fg(if self.focused {
+                Color::Rgb(253, 145, 62)
+            } else {
+                Color::DarkGray
+            }))
+            .highlight_symbol(">> ");
+        f.render_stateful_widget(table, area, &mut self.state);
+        if let Some(id) = &self.confirm_redeploy {
+            self.draw_confirmation(f, area, id);
+        }
         Ok(())
     }
 }
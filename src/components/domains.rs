@@ -0,0 +1,393 @@
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{
+    centered_rect,
+    form::{Field, Form, FormEvent},
+    titled_block, Component, Frame,
+};
+use crate::{
+    action::Action,
+    cache::Snapshot,
+    config::Config,
+    shuttle::models::{validate_domain, Certificate, DnsRecord},
+    tab::Tab,
+    utils::humanize_duration,
+};
+
+/// Certificates that expire within this many days are shown in red.
+const CRITICAL_DAYS: i64 = 7;
+
+/// Certificates that expire within this many days are shown in yellow.
+const WARNING_DAYS: i64 = 30;
+
+#[derive(Default)]
+enum Stage {
+    #[default]
+    List,
+    /// The domain to add is asked for.
+    Adding(Form),
+    /// The DNS record of the new domain is shown before it is added.
+    Dns(DnsRecord),
+    /// The deletion of the domain waits for confirmation.
+    Deleting(String),
+}
+
+/// Full-screen page with the custom domains of a project and their certificates.
+#[derive(Default)]
+pub struct Domains {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    snapshot: Snapshot,
+    /// Project of the open page.
+    project: Option<String>,
+    state: TableState,
+    stage: Stage,
+}
+
+/// Returns the time left until the expiry and the color of its urgency.
+fn countdown(expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> (String, Color) {
+    let Some(expires_at) = expires_at else {
+        return (String::from("unknown"), Color::DarkGray);
+    };
+    let left = expires_at - now;
+    if left.num_seconds() <= 0 {
+        return (
+            format!("expired {} ago", humanize_duration(-left)),
+            Color::Red,
+        );
+    }
+    let color = match left.num_days() {
+        days if days < CRITICAL_DAYS => Color::Red,
+        days if days < WARNING_DAYS => Color::Yellow,
+        _ => Color::Green,
+    };
+    (format!("in {}", humanize_duration(left)), color)
+}
+
+impl Domains {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn certificates(&self) -> &[Certificate] {
+        self.project
+            .as_deref()
+            .map(|project| self.snapshot.state.certificates(project))
+            .unwrap_or_default()
+    }
+
+    fn selected(&self) -> Option<&Certificate> {
+        self.state
+            .selected()
+            .and_then(|index| self.certificates().get(index))
+    }
+
+    fn select(&mut self, offset: isize) {
+        let len = self.certificates().len() as isize;
+        let current = self.state.selected().unwrap_or_default() as isize;
+        self.state
+            .select(Some((current + offset).clamp(0, (len - 1).max(0)) as usize));
+    }
+
+    fn form() -> Form {
+        Form::new(
+            "Add a custom domain",
+            vec![Field::new(
+                "Domain",
+                "Fully qualified domain name, e.g. api.example.com",
+            )
+            .validator(validate_domain)],
+        )
+    }
+
+    fn handle_list_key(&mut self, key: KeyEvent, project: String) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.select(1),
+            KeyCode::Char('k') | KeyCode::Up => self.select(-1),
+            KeyCode::Char('a') | KeyCode::Char('d') | KeyCode::Delete
+                if self.snapshot.is_read_only() =>
+            {
+                log::warn!("Cannot change the domains while offline");
+            }
+            KeyCode::Char('a') => self.stage = Stage::Adding(Self::form()),
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(certificate) = self.selected() {
+                    self.stage = Stage::Deleting(certificate.subject.clone());
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(certificate) = self.selected() {
+                    self.stage = Stage::Dns(DnsRecord::for_domain(&certificate.subject, &project));
+                }
+            }
+            KeyCode::Esc => return Some(Action::CloseDomains),
+            _ => {}
+        }
+        None
+    }
+
+    fn draw_list(&mut self, f: &mut Frame<'_>, area: Rect, project: &str) {
+        let now = Utc::now();
+        let rows = self
+            .certificates()
+            .iter()
+            .map(|certificate| {
+                let expires_at = certificate.expires_at();
+                let (left, color) = countdown(expires_at, now);
+                Row::new(vec![
+                    Cell::from(certificate.subject.clone()),
+                    Cell::from(
+                        expires_at
+                            .map(|v| v.format("%Y-%m-%d %H:%M UTC").to_string())
+                            .unwrap_or_else(|| certificate.not_after.clone()),
+                    ),
+                    Cell::from(left).style(Style::default().fg(color)),
+                    Cell::from(certificate.serial_hex.clone())
+                        .style(Style::default().fg(Color::DarkGray)),
+                ])
+            })
+            .collect::<Vec<_>>();
+        let title = format!("Custom domains of {project}");
+        let block = titled_block(&title).title(
+            block::Title::from("a: add  d: delete  enter: DNS record  esc: back")
+                .alignment(Alignment::Right)
+                .position(block::Position::Bottom),
+        );
+        if rows.is_empty() {
+            f.render_widget(
+                Paragraph::new(vec![
+                    Line::from("No custom domains".italic()),
+                    Line::default(),
+                    Line::from("Press a to add one".dark_gray()),
+                ])
+                .block(block),
+                area,
+            );
+            return;
+        }
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec!["Subject", "Expires", "Left", "Serial"])
+                    .bottom_margin(1)
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .widths(&[
+                Constraint::Percentage(35),
+                Constraint::Length(20),
+                Constraint::Length(16),
+                Constraint::Min(10),
+            ])
+            .column_spacing(2)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        f.render_stateful_widget(table, area, &mut self.state);
+    }
+
+    fn popup(f: &mut Frame<'_>, area: Rect, title: &str, lines: Vec<Line<'_>>) {
+        // Leaves room for the lines that wrap.
+        let rect = centered_rect(area, 70, lines.len() as u16 + 6);
+        f.render_widget(Clear, rect);
+        let block = Block::default()
+            .title(Line::from(title.bold()))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .padding(Padding::new(1, 1, 1, 0));
+        f.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(block),
+            rect,
+        );
+    }
+}
+
+impl Component for Domains {
+    fn assigned_tab(&self) -> Option<Tab> {
+        Some(Tab::Projects)
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.project.is_some()
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        match self.stage {
+            Stage::List => Some("Domains"),
+            _ => Some("Modal"),
+        }
+    }
+
+    fn captures_input(&self) -> bool {
+        matches!(self.stage, Stage::Adding(_))
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let Some(project) = self.project.clone() else {
+            return Ok(None);
+        };
+        let action = match &mut self.stage {
+            Stage::List => self.handle_list_key(key, project),
+            Stage::Adding(form) => {
+                match form.handle_key_events(key) {
+                    Some(FormEvent::Submitted(values)) => {
+                        self.stage = Stage::Dns(DnsRecord::for_domain(&values[0], &project));
+                    }
+                    Some(FormEvent::Cancelled) => self.stage = Stage::List,
+                    None => {}
+                }
+                None
+            }
+            Stage::Dns(record) => match key.code {
+                KeyCode::Enter => {
+                    let known = self
+                        .snapshot
+                        .state
+                        .certificates(&project)
+                        .iter()
+                        .any(|v| v.subject.eq_ignore_ascii_case(&record.name));
+                    let action = (!known).then(|| Action::AddDomain(project, record.name.clone()));
+                    self.stage = Stage::List;
+                    action
+                }
+                KeyCode::Esc => {
+                    self.stage = Stage::List;
+                    None
+                }
+                _ => None,
+            },
+            Stage::Deleting(domain) => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    let action = Action::DeleteDomain(project, domain.clone());
+                    self.stage = Stage::List;
+                    Some(action)
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.stage = Stage::List;
+                    None
+                }
+                _ => None,
+            },
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::PlatformUpdated(snapshot) => {
+                self.snapshot = *snapshot;
+                self.select(0);
+            }
+            Action::OpenDomains(project) => {
+                self.project = Some(project);
+                self.stage = Stage::List;
+                self.state.select(Some(0));
+            }
+            Action::CloseDomains => self.project = None,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let Some(project) = self.project.clone() else {
+            return Ok(());
+        };
+        f.render_widget(Clear, area);
+        self.draw_list(f, area, &project);
+        match &mut self.stage {
+            Stage::List => {}
+            Stage::Adding(form) => form.draw(f, centered_rect(area, 70, form.height())),
+            Stage::Dns(record) => {
+                let field = |name: &'static str, value: String| {
+                    Line::from(vec![Span::from(name).bold(), Span::raw(value)])
+                };
+                let mut lines = vec![
+                    Line::from("1. Create this record at the DNS provider of the domain:"),
+                    Line::default(),
+                    field("   Type    ", record.kind.to_string()),
+                    field("   Name    ", record.name.clone()),
+                    field("   Target  ", record.target.clone()),
+                    Line::default(),
+                ];
+                if record.kind == "ALIAS" {
+                    lines.push(Line::from(
+                        "   Apex domains cannot have a CNAME record. If the provider has no \
+                         ALIAS record, look for ANAME or CNAME flattening."
+                            .dark_gray(),
+                    ));
+                    lines.push(Line::default());
+                }
+                lines.extend([
+                    Line::from(format!(
+                        "2. Wait until `dig {} {}` returns the target.",
+                        if record.kind == "CNAME" { "CNAME" } else { "A" },
+                        record.name
+                    )),
+                    Line::from("3. Add the domain, after which the certificate is issued."),
+                    Line::default(),
+                    Line::from("Enter: add the domain  Esc: back".dark_gray()),
+                ]);
+                Self::popup(f, area, &format!("DNS record for {}", record.name), lines);
+            }
+            Stage::Deleting(domain) => Self::popup(
+                f,
+                area,
+                "Delete custom domain",
+                vec![
+                    Line::from(format!(
+                        "Delete {domain} and its certificate from {project}?"
+                    )),
+                    Line::default(),
+                    Line::from("y: delete  n: cancel".dark_gray()),
+                ],
+            ),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_countdown() {
+        let now = Utc::now();
+        assert_eq!(
+            countdown(Some(now + Duration::days(90)), now),
+            (String::from("in 90d"), Color::Green)
+        );
+        assert_eq!(
+            countdown(Some(now + Duration::days(20)), now),
+            (String::from("in 20d"), Color::Yellow)
+        );
+        assert_eq!(
+            countdown(Some(now + Duration::hours(5)), now),
+            (String::from("in 5h"), Color::Red)
+        );
+        assert_eq!(
+            countdown(Some(now - Duration::days(2)), now),
+            (String::from("expired 2d ago"), Color::Red)
+        );
+        assert_eq!(countdown(None, now).1, Color::DarkGray);
+    }
+}
@@ -0,0 +1,183 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+
+use super::Frame;
+
+/// Multi-line text input.
+///
+/// Only plain editing keys are handled, so that the owner can bind the
+/// control combinations.
+#[derive(Debug, Clone)]
+pub struct Editor {
+    lines: Vec<String>,
+    /// Line and character of the cursor.
+    cursor: (usize, usize),
+    /// First line that is shown.
+    scroll: usize,
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            cursor: (0, 0),
+            scroll: 0,
+        }
+    }
+}
+
+impl Editor {
+    pub fn new(text: &str) -> Self {
+        let mut editor = Self::default();
+        editor.set_text(text);
+        editor
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Replaces the text and moves the cursor to its end.
+    pub fn set_text(&mut self, text: &str) {
+        self.lines = text.split('\n').map(String::from).collect();
+        let row = self.lines.len() - 1;
+        self.cursor = (row, self.lines[row].chars().count());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|line| line.is_empty())
+    }
+
+    fn byte_index(&self) -> usize {
+        let (row, col) = self.cursor;
+        self.lines[row]
+            .char_indices()
+            .nth(col)
+            .map_or(self.lines[row].len(), |(i, _)| i)
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    /// Applies an editing key and returns `true` if it was handled.
+    pub fn handle_key_events(&mut self, key: KeyEvent) -> bool {
+        if key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return false;
+        }
+        let (row, col) = self.cursor;
+        match key.code {
+            KeyCode::Char(c) => {
+                let index = self.byte_index();
+                self.lines[row].insert(index, c);
+                self.cursor.1 += 1;
+            }
+            KeyCode::Tab => {
+                let index = self.byte_index();
+                self.lines[row].insert_str(index, "  ");
+                self.cursor.1 += 2;
+            }
+            KeyCode::Enter => {
+                let index = self.byte_index();
+                let rest = self.lines[row].split_off(index);
+                self.lines.insert(row + 1, rest);
+                self.cursor = (row + 1, 0);
+            }
+            KeyCode::Backspace if col > 0 => {
+                self.cursor.1 -= 1;
+                let index = self.byte_index();
+                self.lines[row].remove(index);
+            }
+            KeyCode::Backspace if row > 0 => {
+                let line = self.lines.remove(row);
+                self.cursor = (row - 1, self.line_len(row - 1));
+                self.lines[row - 1].push_str(&line);
+            }
+            KeyCode::Delete if col < self.line_len(row) => {
+                let index = self.byte_index();
+                self.lines[row].remove(index);
+            }
+            KeyCode::Delete if row + 1 < self.lines.len() => {
+                let line = self.lines.remove(row + 1);
+                self.lines[row].push_str(&line);
+            }
+            KeyCode::Left if col > 0 => self.cursor.1 -= 1,
+            KeyCode::Left if row > 0 => self.cursor = (row - 1, self.line_len(row - 1)),
+            KeyCode::Right if col < self.line_len(row) => self.cursor.1 += 1,
+            KeyCode::Right if row + 1 < self.lines.len() => self.cursor = (row + 1, 0),
+            KeyCode::Up if row > 0 => {
+                self.cursor = (row - 1, col.min(self.line_len(row - 1)));
+            }
+            KeyCode::Down if row + 1 < self.lines.len() => {
+                self.cursor = (row + 1, col.min(self.line_len(row + 1)));
+            }
+            KeyCode::Home => self.cursor.1 = 0,
+            KeyCode::End => self.cursor.1 = self.line_len(row),
+            KeyCode::Backspace
+            | KeyCode::Delete
+            | KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::Up
+            | KeyCode::Down => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// Draws the text in the block, with the cursor if it is focused.
+    pub fn draw(&mut self, f: &mut Frame<'_>, area: Rect, block: Block<'_>, focused: bool) {
+        let inner = block.inner(area);
+        let height = inner.height.max(1) as usize;
+        let row = self.cursor.0;
+        if row < self.scroll {
+            self.scroll = row;
+        } else if row >= self.scroll + height {
+            self.scroll = row + 1 - height;
+        }
+        let lines = self
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .map(|line| Line::from(line.clone()))
+            .collect::<Vec<_>>();
+        f.render_widget(Paragraph::new(lines).block(block), area);
+        if focused {
+            let col = self.cursor.1 as u16;
+            f.set_cursor(
+                (inner.x + col).min(inner.right().saturating_sub(1)),
+                inner.y + (row - self.scroll) as u16,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_editing() {
+        let mut editor = Editor::new("SELECT 1");
+        let keys = [
+            KeyCode::Enter,
+            KeyCode::Char('é'),
+            KeyCode::Up,
+            KeyCode::Home,
+            KeyCode::Delete,
+            KeyCode::Char('s'),
+            KeyCode::Down,
+            KeyCode::Home,
+            KeyCode::Backspace,
+        ];
+        for code in keys {
+            assert!(editor.handle_key_events(KeyEvent::from(code)));
+        }
+        assert_eq!(editor.text(), "sELECT 1é");
+        assert!(!editor.handle_key_events(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)));
+    }
+}
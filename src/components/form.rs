@@ -0,0 +1,225 @@
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use super::Frame;
+
+/// Checks the value of a field and returns the message to show if it is invalid.
+pub type Validator = fn(&str) -> Result<(), String>;
+
+/// Single input of a form.
+#[derive(Debug, Clone)]
+pub struct Field {
+    label: &'static str,
+    help: &'static str,
+    input: Input,
+    validator: Option<Validator>,
+    error: Option<String>,
+}
+
+impl Field {
+    pub fn new(label: &'static str, help: &'static str) -> Self {
+        Self {
+            label,
+            help,
+            input: Input::default(),
+            validator: None,
+            error: None,
+        }
+    }
+
+    /// Sets the initial value of the field.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.input = Input::new(value.into());
+        self
+    }
+
+    pub fn validator(mut self, validator: Validator) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    fn validate(&mut self) -> bool {
+        self.error = self
+            .validator
+            .and_then(|validate| validate(self.input.value().trim()).err());
+        self.error.is_none()
+    }
+}
+
+/// Outcome of a key event in a form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormEvent {
+    /// Every field is valid and the last one was confirmed.
+    Submitted(Vec<String>),
+    Cancelled,
+}
+
+/// Form that asks for its fields one step at a time.
+///
+/// A field is validated when it is confirmed with Enter, so that the next
+/// step is only shown for a valid value. Shift-Tab goes back a step and Esc
+/// cancels the form.
+#[derive(Debug, Clone)]
+pub struct Form {
+    title: String,
+    fields: Vec<Field>,
+    step: usize,
+}
+
+impl Form {
+    pub fn new(title: impl Into<String>, fields: Vec<Field>) -> Self {
+        Self {
+            title: title.into(),
+            fields,
+            step: 0,
+        }
+    }
+
+    /// Returns the trimmed values of the fields.
+    pub fn values(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .map(|field| field.input.value().trim().to_string())
+            .collect()
+    }
+
+    pub fn handle_key_events(&mut self, key: KeyEvent) -> Option<FormEvent> {
+        let last = self.step + 1 == self.fields.len();
+        let field = self.fields.get_mut(self.step)?;
+        match key.code {
+            KeyCode::Esc => return Some(FormEvent::Cancelled),
+            KeyCode::Enter if !field.validate() => {}
+            KeyCode::Enter if last => {
+                return Some(FormEvent::Submitted(self.values()));
+            }
+            KeyCode::Enter => self.step += 1,
+            KeyCode::BackTab => self.step = self.step.saturating_sub(1),
+            _ => {
+                field.input.handle_event(&CrosstermEvent::Key(key));
+                field.error = None;
+            }
+        }
+        None
+    }
+
+    /// Returns the height that the form needs, including its border.
+    pub fn height(&self) -> u16 {
+        self.fields.len() as u16 + 9
+    }
+
+    pub fn draw(&self, f: &mut Frame<'_>, area: Rect) {
+        let block = Block::default()
+            .title(Line::from(vec![
+                Span::styled(
+                    self.title.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" ({}/{})", self.step + 1, self.fields.len())),
+            ]))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(area);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+
+        let Some(field) = self.fields.get(self.step) else {
+            return;
+        };
+        let mut lines = self.fields[..self.step]
+            .iter()
+            .map(|field| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{}: ", field.label),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(field.input.value().to_string()),
+                ])
+            })
+            .collect::<Vec<_>>();
+        lines.push(Line::default());
+        lines.push(Line::from(field.label.bold()));
+        lines.push(Line::from(Span::styled(
+            field.help,
+            Style::default().fg(Color::DarkGray),
+        )));
+        let input_y = inner.y + lines.len() as u16;
+        lines.push(Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Rgb(253, 145, 62))),
+            Span::raw(field.input.value().to_string()),
+        ]));
+        lines.push(match &field.error {
+            Some(error) => Line::from(error.clone().red()),
+            None => Line::default(),
+        });
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            "Enter: next  Shift-Tab: back  Esc: cancel",
+            Style::default().fg(Color::DarkGray),
+        )));
+        f.render_widget(Paragraph::new(lines), inner);
+
+        let scroll = field
+            .input
+            .visual_scroll(inner.width.saturating_sub(3) as usize);
+        let cursor = (field.input.visual_cursor().saturating_sub(scroll)) as u16;
+        if input_y < inner.bottom() {
+            f.set_cursor((inner.x + 2 + cursor).min(inner.right()), input_y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_form_steps() {
+        let mut form = Form::new(
+            "Test",
+            vec![
+                Field::new("Name", "").validator(|v| {
+                    if v.is_empty() {
+                        Err(String::from("required"))
+                    } else {
+                        Ok(())
+                    }
+                }),
+                Field::new("Size", "").value("3"),
+            ],
+        );
+        assert_eq!(form.handle_key_events(key(KeyCode::Enter)), None);
+        assert_eq!(form.step, 0);
+        assert_eq!(form.fields[0].error.as_deref(), Some("required"));
+
+        form.handle_key_events(key(KeyCode::Char('a')));
+        assert_eq!(form.fields[0].error, None);
+        assert_eq!(form.handle_key_events(key(KeyCode::Enter)), None);
+        assert_eq!(form.step, 1);
+        form.handle_key_events(key(KeyCode::BackTab));
+        assert_eq!(form.step, 0);
+        form.handle_key_events(key(KeyCode::Enter));
+        assert_eq!(
+            form.handle_key_events(key(KeyCode::Enter)),
+            Some(FormEvent::Submitted(vec![
+                String::from("a"),
+                String::from("3")
+            ]))
+        );
+        assert_eq!(
+            form.handle_key_events(key(KeyCode::Esc)),
+            Some(FormEvent::Cancelled)
+        );
+    }
+}
@@ -11,6 +11,7 @@ use super::{Component, Frame};
 use crate::{
     action::Action,
     config::{Config, KeyBindings},
+    manifest::Workspace,
     tab::Tab,
 };
 
@@ -47,12 +48,80 @@ pub struct Home {
     config: Config,
     pub input: Input,
     pub text: Vec<String>,
+    workspace: Option<Workspace>,
+    /// Runtime version that the platform supports.
+    platform_version: Option<String>,
 }
 
 impl Home {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Draws the Shuttle service of the current directory, if any.
+    fn draw_workspace(&self, f: &mut Frame<'_>, area: Rect) {
+        let mut lines = Vec::new();
+        match &self.workspace {
+            Some(workspace) if workspace.is_shuttle_service() => {
+                let field = |name: &'static str, value: String| {
+                    Line::from(vec![Span::from(name).bold(), Span::raw(value)])
+                };
+                if let Some(name) = workspace.project_name() {
+                    lines.push(field("Project: ", name.to_string()));
+                }
+                if let Some(shuttle) = &workspace.shuttle {
+                    if !shuttle.assets.is_empty() {
+                        lines.push(field("Assets: ", shuttle.assets.join(", ")));
+                    }
+                    if !shuttle.deploy_include.is_empty() {
+                        lines.push(field("Deploy include: ", shuttle.deploy_include.join(", ")));
+                    }
+                }
+                if let Some(version) = &self.platform_version {
+                    if !workspace.mismatches(version).is_empty() {
+                        lines.push(Line::from(
+                            format!("Update the Shuttle crates to {version}").yellow(),
+                        ));
+                    }
+                }
+                lines.push(Line::default());
+                for dependency in &workspace.dependencies {
+                    let version = dependency.version.as_deref().unwrap_or("local");
+                    let mismatched = self
+                        .platform_version
+                        .as_ref()
+                        .is_some_and(|v| dependency.is_mismatched(v));
+                    let mut spans = vec![
+                        Span::raw(format!("{} ", dependency.name)),
+                        Span::styled(
+                            version.to_string(),
+                            Style::default().fg(if mismatched { Color::Red } else { Color::Green }),
+                        ),
+                    ];
+                    if let (true, Some(platform)) = (mismatched, &self.platform_version) {
+                        spans.push(Span::styled(
+                            format!(" (platform runs {platform})"),
+                            Style::default().fg(Color::Red),
+                        ));
+                    }
+                    lines.push(Line::from(spans));
+                }
+            }
+            Some(_) => lines.push(Line::from("No Shuttle service in this directory".italic())),
+            None => lines.push(Line::from("Not a Cargo project".italic())),
+        }
+        f.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title("Workspace")
+                    .title_alignment(Alignment::Center)
+                    .padding(Padding::horizontal(1)),
+            ),
+            area,
+        );
+    }
 }
 
 impl Component for Home {
@@ -74,16 +143,16 @@ impl Component for Home {
         match action {
             Action::Tick => {}
             Action::ToggleShowHelp => self.show_help = !self.show_help,
+            Action::WorkspaceDetected(workspace) => self.workspace = Some(*workspace),
+            Action::PlatformUpdated(snapshot) => {
+                self.platform_version = snapshot.state.version.clone();
+            }
             _ => {}
         }
         Ok(None)
     }
 
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
-        let area = Layout::default()
-            .constraints(vec![Constraint::Percentage(100)])
-            .margin(3)
-            .split(area)[0];
         let rect = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![Constraint::Min(13), Constraint::Percentage(100)])
@@ -105,7 +174,7 @@ impl Component for Home {
             .split(rect[1]);
 
         {
-            let rect = Layout::default()
+            let columns = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints(vec![
                     Constraint::Percentage(33),
@@ -113,10 +182,11 @@ impl Component for Home {
                     Constraint::Percentage(33),
                 ])
                 .split(rect[0]);
+            self.draw_workspace(f, columns[0]);
             let rect = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
-                .split(rect[1]);
+                .split(columns[1]);
             {
                 f.render_widget(
                     Block::default()
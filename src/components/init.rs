@@ -0,0 +1,394 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use super::{
+    centered_rect,
+    form::{Field, Form, FormEvent},
+    new_project::DEFAULT_IDLE_MINUTES,
+    Component, Frame,
+};
+use crate::{
+    action::Action,
+    config::Config,
+    scaffold::{self, Template},
+    shuttle::models::Project,
+};
+
+#[derive(Default)]
+enum Stage {
+    #[default]
+    Closed,
+    /// A template is picked from the filtered list.
+    Choosing { query: Input, state: ListState },
+    /// The name and the directory of the project are asked for.
+    Details { template: Template, form: Form },
+    /// The files are shown before they are written.
+    Preview {
+        template: Template,
+        name: String,
+        directory: PathBuf,
+        files: Vec<(PathBuf, String)>,
+        state: ListState,
+        scroll: u16,
+        create_remote: bool,
+        error: Option<String>,
+    },
+}
+
+/// Flow that writes a new project from a template, like `cargo shuttle init`.
+#[derive(Default)]
+pub struct Init {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    templates: Vec<Template>,
+    stage: Stage,
+}
+
+impl Init {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn filtered<'a>(templates: &'a [Template], query: &Input) -> Vec<&'a Template> {
+        templates
+            .iter()
+            .filter(|template| template.matches(query.value()))
+            .collect()
+    }
+
+    fn details(template: Template) -> Stage {
+        Stage::Details {
+            template,
+            form: Form::new(
+                "New project from a template",
+                vec![
+                    Field::new(
+                        "Name",
+                        "Name of the crate and of the project on the platform",
+                    )
+                    .validator(Project::validate_name),
+                    Field::new(
+                        "Directory",
+                        "Empty or missing directory, ./<name> if left empty",
+                    )
+                    .validator(validate_directory),
+                ],
+            ),
+        }
+    }
+
+    fn preview(template: Template, values: Vec<String>) -> Stage {
+        let name = values[0].clone();
+        let directory = match values[1].as_str() {
+            "" => PathBuf::from(&name),
+            directory => PathBuf::from(directory),
+        };
+        let (files, error) = match template.render(&name) {
+            Ok(files) => (files, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        Stage::Preview {
+            template,
+            name,
+            directory,
+            files,
+            state: ListState::default().with_selected(Some(0)),
+            scroll: 0,
+            create_remote: false,
+            error,
+        }
+    }
+
+    fn handle_preview_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let Stage::Preview {
+            name,
+            directory,
+            files,
+            state,
+            scroll,
+            create_remote,
+            error,
+            ..
+        } = &mut self.stage
+        else {
+            return None;
+        };
+        let selected = state.selected().unwrap_or_default();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                state.select(Some((selected + 1).min(files.len().saturating_sub(1))));
+                *scroll = 0;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                state.select(Some(selected.saturating_sub(1)));
+                *scroll = 0;
+            }
+            KeyCode::PageDown => *scroll = scroll.saturating_add(10),
+            KeyCode::PageUp => *scroll = scroll.saturating_sub(10),
+            KeyCode::Char('c') => *create_remote = !*create_remote,
+            KeyCode::Enter => match scaffold::write(directory, files) {
+                Ok(()) => {
+                    let message = format!("Created {name} in {}", directory.display());
+                    log::info!("{message}");
+                    if let Some(tx) = &self.command_tx {
+                        let _ = tx.send(Action::Notify(message));
+                        if *create_remote {
+                            let _ =
+                                tx.send(Action::CreateProject(name.clone(), DEFAULT_IDLE_MINUTES));
+                        }
+                    }
+                    self.stage = Stage::Closed;
+                }
+                Err(e) => *error = Some(e.to_string()),
+            },
+            KeyCode::BackTab | KeyCode::Esc => {
+                let Stage::Preview { template, .. } = std::mem::take(&mut self.stage) else {
+                    return None;
+                };
+                self.stage = Self::details(template);
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Accepts a directory that is missing or empty.
+fn validate_directory(directory: &str) -> Result<(), String> {
+    let empty = Path::new(directory)
+        .read_dir()
+        .map_or(true, |mut entries| entries.next().is_none());
+    if directory.is_empty() || empty {
+        Ok(())
+    } else {
+        Err(format!("{directory} is not empty"))
+    }
+}
+
+impl Component for Init {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        !matches!(self.stage, Stage::Closed)
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("Modal")
+    }
+
+    fn captures_input(&self) -> bool {
+        matches!(self.stage, Stage::Choosing { .. } | Stage::Details { .. })
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match &mut self.stage {
+            Stage::Closed => {}
+            Stage::Choosing { query, state } => {
+                let len = Self::filtered(&self.templates, query).len();
+                let selected = state.selected().unwrap_or_default();
+                let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                match (key.code, ctrl) {
+                    (KeyCode::Esc, _) => self.stage = Stage::Closed,
+                    (KeyCode::Down, _) | (KeyCode::Char('n'), true) => {
+                        state.select(Some((selected + 1).min(len.saturating_sub(1))));
+                    }
+                    (KeyCode::Up, _) | (KeyCode::Char('p'), true) => {
+                        state.select(Some(selected.saturating_sub(1)));
+                    }
+                    (KeyCode::Enter, _) => {
+                        if let Some(template) = Self::filtered(&self.templates, query).get(selected)
+                        {
+                            self.stage = Self::details((*template).clone());
+                        }
+                    }
+                    _ => {
+                        query.handle_event(&CrosstermEvent::Key(key));
+                        state.select(Some(0));
+                    }
+                }
+            }
+            Stage::Details { form, .. } => match form.handle_key_events(key) {
+                Some(FormEvent::Submitted(values)) => {
+                    let Stage::Details { template, .. } = std::mem::take(&mut self.stage) else {
+                        return Ok(None);
+                    };
+                    self.stage = Self::preview(template, values);
+                }
+                Some(FormEvent::Cancelled) => {
+                    self.stage = Stage::Choosing {
+                        query: Input::default(),
+                        state: ListState::default().with_selected(Some(0)),
+                    }
+                }
+                None => {}
+            },
+            Stage::Preview { .. } => return Ok(self.handle_preview_key(key)),
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let (Action::InitProject, Stage::Closed) = (action, &self.stage) {
+            self.templates = scaffold::templates(&scaffold::local_directory());
+            self.stage = Stage::Choosing {
+                query: Input::default(),
+                state: ListState::default().with_selected(Some(0)),
+            };
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let block = |title: String| {
+            Block::default()
+                .title(Line::from(title.bold()))
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Yellow))
+                .padding(Padding::horizontal(1))
+        };
+        match &mut self.stage {
+            Stage::Closed => {}
+            Stage::Choosing { query, state } => {
+                let templates = Self::filtered(&self.templates, query);
+                let rect = centered_rect(area, 80, templates.len().max(1) as u16 + 5);
+                f.render_widget(Clear, rect);
+                let block = block(String::from("New project from a template"));
+                let inner = block.inner(rect);
+                f.render_widget(block, rect);
+                let rect = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(2), Constraint::Min(1)])
+                    .split(inner);
+                f.render_widget(
+                    Paragraph::new(Line::from(vec![
+                        Span::styled("Search: ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(query.value().to_string()),
+                    ])),
+                    rect[0],
+                );
+                f.set_cursor(rect[0].x + 8 + query.visual_cursor() as u16, rect[0].y);
+                let items = templates
+                    .iter()
+                    .map(|template| {
+                        ListItem::new(Line::from(vec![
+                            Span::styled(
+                                format!("{:<12}", template.name),
+                                Style::default().add_modifier(Modifier::BOLD),
+                            ),
+                            Span::styled(
+                                template.description.clone(),
+                                Style::default().fg(Color::DarkGray),
+                            ),
+                        ]))
+                    })
+                    .collect::<Vec<_>>();
+                let list = if items.is_empty() {
+                    List::new(vec![ListItem::new("No matching templates".italic())])
+                } else {
+                    List::new(items)
+                };
+                f.render_stateful_widget(
+                    list.highlight_style(Style::default().fg(Color::Rgb(253, 145, 62)))
+                        .highlight_symbol(">> "),
+                    rect[1],
+                    state,
+                );
+            }
+            Stage::Details { form, .. } => form.draw(f, centered_rect(area, 80, form.height())),
+            Stage::Preview {
+                template,
+                name,
+                directory,
+                files,
+                state,
+                scroll,
+                create_remote,
+                error,
+            } => {
+                let rect = area.inner(&Margin {
+                    horizontal: 6,
+                    vertical: 3,
+                });
+                f.render_widget(Clear, rect);
+                let block = block(format!("{} from {}", name, template.name));
+                let inner = block.inner(rect);
+                f.render_widget(block, rect);
+                let rect = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(2),
+                        Constraint::Min(1),
+                        Constraint::Length(1),
+                    ])
+                    .split(inner);
+                f.render_widget(
+                    Paragraph::new(vec![
+                        Line::from(vec![
+                            "Directory: ".bold(),
+                            Span::raw(directory.display().to_string()),
+                        ]),
+                        match error {
+                            Some(error) => Line::from(error.clone().red()),
+                            None => Line::default(),
+                        },
+                    ]),
+                    rect[0],
+                );
+                let columns = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+                    .split(rect[1]);
+                let items = files
+                    .iter()
+                    .map(|(path, _)| ListItem::new(path.display().to_string()))
+                    .collect::<Vec<_>>();
+                f.render_stateful_widget(
+                    List::new(items)
+                        .block(Block::default().borders(Borders::RIGHT))
+                        .highlight_style(Style::default().fg(Color::Rgb(253, 145, 62)))
+                        .highlight_symbol(">> "),
+                    columns[0],
+                    state,
+                );
+                let content = state
+                    .selected()
+                    .and_then(|index| files.get(index))
+                    .map(|(_, content)| content.as_str())
+                    .unwrap_or_default();
+                f.render_widget(
+                    Paragraph::new(content)
+                        .scroll((*scroll, 0))
+                        .block(Block::default().padding(Padding::horizontal(1))),
+                    columns[1],
+                );
+                let remote = if *create_remote { "[x]" } else { "[ ]" };
+                f.render_widget(
+                    Paragraph::new(Line::from(vec![
+                        Span::raw(format!("{remote} Create the project on the platform  ")),
+                        Span::styled(
+                            "c: toggle  Enter: write  Esc: back",
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ])),
+                    rect[2],
+                );
+            }
+        }
+        Ok(())
+    }
+}
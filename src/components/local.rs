@@ -0,0 +1,218 @@
+use std::{collections::VecDeque, time::Instant};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Component, Frame};
+use crate::{action::Action, ansi, config::Config, tab::Tab, utils::humanize_duration};
+
+/// Maximum number of output lines that are kept.
+const MAX_LINES: usize = 5000;
+
+/// State of the local process.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Status {
+    #[default]
+    Idle,
+    Starting,
+    Running {
+        pid: Option<u32>,
+        started_at: Instant,
+    },
+    Exited(Option<i32>),
+    Stopped,
+}
+
+/// Tab that supervises the local run of the service.
+#[derive(Default)]
+pub struct Local {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    status: Status,
+    output: VecDeque<Line<'static>>,
+    /// Number of lines scrolled up from the bottom.
+    scroll: usize,
+    restarts: usize,
+}
+
+impl Local {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn status_line(&self) -> Line<'static> {
+        let (text, color) = match self.status {
+            Status::Idle => (String::from("not running"), Color::DarkGray),
+            Status::Starting => (String::from("starting…"), Color::Yellow),
+            Status::Running { pid, started_at } => (
+                format!(
+                    "● running (pid {}) for {}",
+                    pid.map(|v| v.to_string())
+                        .unwrap_or_else(|| String::from("?")),
+                    humanize_duration(
+                        chrono::Duration::from_std(started_at.elapsed()).unwrap_or_default()
+                    )
+                ),
+                Color::Green,
+            ),
+            Status::Exited(Some(0)) => (String::from("exited successfully"), Color::Green),
+            Status::Exited(Some(code)) => (format!("exited with status {code}"), Color::Red),
+            Status::Exited(None) => (String::from("terminated"), Color::Red),
+            Status::Stopped => (String::from("stopped"), Color::DarkGray),
+        };
+        let mut spans = vec![
+            Span::styled(format!(" {text} "), Style::default().fg(color).bold()),
+            Span::styled(
+                format!("· {} ", self.config.local.command),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+        if self.restarts > 0 {
+            spans.push(Span::styled(
+                format!("· {} restarts ", self.restarts),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        if self.config.local.restart_on_change {
+            let paths = self
+                .config
+                .local
+                .watch
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            spans.push(Span::styled(
+                format!("· watching {paths}"),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Component for Local {
+    fn assigned_tab(&self) -> Option<Tab> {
+        Some(Tab::Local)
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let max = self.output.len().saturating_sub(1);
+        let action = match key.code {
+            KeyCode::Char('s') => Some(Action::StartLocal),
+            KeyCode::Char('R') => Some(Action::RestartLocal),
+            KeyCode::Char('x') => Some(Action::StopLocal),
+            KeyCode::Char('c') => {
+                self.output.clear();
+                self.scroll = 0;
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.scroll = (self.scroll + 1).min(max);
+                None
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.scroll = self.scroll.saturating_sub(1);
+                None
+            }
+            KeyCode::Char('g') | KeyCode::Home => {
+                self.scroll = max;
+                None
+            }
+            KeyCode::Char('G') | KeyCode::End => {
+                self.scroll = 0;
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::StartLocal if !matches!(self.status, Status::Running { .. }) => {
+                self.status = Status::Starting
+            }
+            Action::RestartLocal => {
+                if self.status != Status::Idle {
+                    self.restarts += 1;
+                }
+                self.status = Status::Starting
+            }
+            Action::LocalStarted(pid) => {
+                self.status = Status::Running {
+                    pid,
+                    started_at: Instant::now(),
+                };
+            }
+            Action::LocalOutput(line) => {
+                if self.output.len() == MAX_LINES {
+                    self.output.pop_front();
+                } else if self.scroll > 0 {
+                    // Keep the viewport in place while scrolled up.
+                    self.scroll += 1;
+                }
+                self.output.push_back(ansi::parse_line(&line));
+            }
+            Action::LocalExited(code) => self.status = Status::Exited(code),
+            Action::LocalStopped if self.status != Status::Starting => {
+                self.status = Status::Stopped
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let rect = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(area);
+        let height = rect[0].height.saturating_sub(2) as usize;
+        let end = self.output.len().saturating_sub(self.scroll);
+        let lines = self
+            .output
+            .range(end.saturating_sub(height)..end)
+            .cloned()
+            .collect::<Vec<_>>();
+        let paragraph = if self.output.is_empty() {
+            Paragraph::new(Line::from("Press s to start the local run".italic()))
+        } else {
+            Paragraph::new(lines)
+        };
+        f.render_widget(
+            paragraph.block(
+                Block::default()
+                    .title(Line::from("Local run".bold()))
+                    .title(
+                        block::Title::from(
+                            "s: start · R: restart · x: stop · c: clear · j/k: scroll",
+                        )
+                        .alignment(Alignment::Right)
+                        .position(block::Position::Bottom),
+                    )
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            ),
+            rect[0],
+        );
+        f.render_widget(Paragraph::new(self.status_line()), rect[1]);
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{titled_block, Component, Frame};
+use crate::{action::Action, config::Config, shuttle::models::LogItem, tab::Tab};
+
+/// View that streams the logs of a deployment.
+#[derive(Default)]
+pub struct LogViewer {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// Project and ID of the deployment whose logs are shown.
+    open: Option<(String, String)>,
+    logs: Vec<LogItem>,
+    /// Number of lines scrolled up from the bottom.
+    scroll: usize,
+}
+
+impl LogViewer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn is_open(&self, project: &str, id: &str) -> bool {
+        self.open
+            .as_ref()
+            .is_some_and(|(open_project, open_id)| open_project == project && open_id == id)
+    }
+
+    /// Returns `true` if new lines are followed automatically.
+    fn is_following(&self) -> bool {
+        self.scroll == 0
+    }
+}
+
+impl Component for LogViewer {
+    fn assigned_tab(&self) -> Option<Tab> {
+        Some(Tab::Logs)
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.open.is_some()
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("LogViewer")
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.open.is_none() {
+            return Ok(None);
+        }
+        let max = self.logs.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc => return Ok(Some(Action::CloseLogs)),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = (self.scroll + 1).min(max),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Char('g') | KeyCode::Home => self.scroll = max,
+            KeyCode::Char('G') | KeyCode::End => self.scroll = 0,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::OpenLogs(project, id) => {
+                self.open = Some((project, id));
+                self.logs.clear();
+                self.scroll = 0;
+            }
+            Action::CloseLogs => {
+                self.open = None;
+                self.logs.clear();
+            }
+            Action::LogsFetched(project, id, logs) if self.is_open(&project, &id) => {
+                // Keep the viewport in place while scrolled up.
+                if !self.is_following() {
+                    self.scroll += logs.len().saturating_sub(self.logs.len());
+                }
+                self.logs = logs;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let Some((project, id)) = &self.open else {
+            f.render_widget(
+                Paragraph::new(Line::from(
+                    "Press L on a deployment to follow its logs".italic(),
+                ))
+                .block(titled_block("Logs")),
+                area,
+            );
+            return Ok(());
+        };
+        let height = area.height.saturating_sub(2) as usize;
+        let end = self.logs.len().saturating_sub(self.scroll);
+        let lines = self.logs[end.saturating_sub(height)..end]
+            .iter()
+            .map(|log| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", log.timestamp.format("%H:%M:%S")),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        format!("[{}] ", log.internal_origin),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(strip_ansi_escapes::strip_str(&log.line)),
+                ])
+            })
+            .collect::<Vec<Line>>();
+        let status = if self.is_following() {
+            String::from("following")
+        } else {
+            format!("{} lines below", self.scroll)
+        };
+        let short_id = id.get(..7).unwrap_or(id);
+        let block = titled_block("Logs")
+            .title(format!(" {project} · {short_id} ({status})"))
+            .title(
+                block::Title::from("j/k: scroll · g/G: top/bottom · esc: back")
+                    .alignment(Alignment::Right)
+                    .position(block::Position::Bottom),
+            );
+        let paragraph = if lines.is_empty() {
+            Paragraph::new(Line::from("Waiting for logs…".italic()))
+        } else {
+            Paragraph::new(lines)
+        };
+        f.render_widget(paragraph.block(block), area);
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{
+    centered_rect,
+    form::{Field, Form, FormEvent},
+    projects::project_state_color,
+    Component, Frame,
+};
+use crate::{
+    action::Action,
+    config::Config,
+    shuttle::models::{Project, ProjectState},
+};
+
+/// Idle minutes of a new project when the field is left empty.
+pub const DEFAULT_IDLE_MINUTES: u64 = 30;
+
+#[derive(Default)]
+enum Stage {
+    #[default]
+    Closed,
+    Editing(Form),
+    /// The project was requested and its state is followed until it is ready.
+    Provisioning {
+        name: String,
+        state: Option<ProjectState>,
+        error: Option<String>,
+    },
+}
+
+/// Wizard that creates a project and shows its provisioning state.
+#[derive(Default)]
+pub struct NewProject {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    stage: Stage,
+}
+
+impl NewProject {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn form() -> Form {
+        Form::new(
+            "New project",
+            vec![
+                Field::new(
+                    "Name",
+                    "Lowercase letters, digits and dashes, used in the URL",
+                )
+                .validator(Project::validate_name),
+                Field::new(
+                    "Idle minutes",
+                    "Minutes without traffic before the project sleeps, 0 to never sleep",
+                )
+                .value(DEFAULT_IDLE_MINUTES.to_string())
+                .validator(|value| {
+                    parse_idle_minutes(value)
+                        .map(|_| ())
+                        .map_err(|_| String::from("Enter a number of minutes"))
+                }),
+            ],
+        )
+    }
+}
+
+fn parse_idle_minutes(value: &str) -> Result<u64, std::num::ParseIntError> {
+    if value.is_empty() {
+        Ok(DEFAULT_IDLE_MINUTES)
+    } else {
+        value.parse()
+    }
+}
+
+impl Component for NewProject {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        !matches!(self.stage, Stage::Closed)
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("Modal")
+    }
+
+    fn captures_input(&self) -> bool {
+        matches!(self.stage, Stage::Editing(_))
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match &mut self.stage {
+            Stage::Closed => None,
+            Stage::Editing(form) => match form.handle_key_events(key) {
+                Some(FormEvent::Submitted(values)) => {
+                    let name = values[0].clone();
+                    let idle_minutes = parse_idle_minutes(&values[1])?;
+                    self.stage = Stage::Provisioning {
+                        name: name.clone(),
+                        state: None,
+                        error: None,
+                    };
+                    Some(Action::CreateProject(name, idle_minutes))
+                }
+                Some(FormEvent::Cancelled) => {
+                    self.stage = Stage::Closed;
+                    None
+                }
+                None => None,
+            },
+            // The provisioning goes on in the background once the wizard is closed.
+            Stage::Provisioning { name, state, .. } => match key.code {
+                KeyCode::Enter if *state == Some(ProjectState::Ready) => {
+                    let name = name.clone();
+                    self.stage = Stage::Closed;
+                    Some(Action::SelectProject(name))
+                }
+                KeyCode::Enter | KeyCode::Esc => {
+                    self.stage = Stage::Closed;
+                    None
+                }
+                _ => None,
+            },
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match (action, &mut self.stage) {
+            (Action::NewProject, Stage::Closed) => self.stage = Stage::Editing(Self::form()),
+            // Projects that are created elsewhere, such as by the init flow, are followed too.
+            (Action::CreateProject(name, _), Stage::Closed) => {
+                self.stage = Stage::Provisioning {
+                    name,
+                    state: None,
+                    error: None,
+                }
+            }
+            (Action::ProjectProvisioning(project), Stage::Provisioning { name, state, .. })
+                if project.name == *name =>
+            {
+                *state = Some(project.state);
+            }
+            (
+                Action::ProjectCreationFailed(project, message),
+                Stage::Provisioning { name, error, .. },
+            ) if project == *name => *error = Some(message),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        match &self.stage {
+            Stage::Closed => {}
+            Stage::Editing(form) => form.draw(f, centered_rect(area, 70, form.height())),
+            Stage::Provisioning { name, state, error } => {
+                let mut lines = vec![Line::from(vec![
+                    "Project: ".bold(),
+                    Span::raw(name.clone()),
+                ])];
+                let (status, hint) = match (state, error) {
+                    (_, Some(error)) => (
+                        Span::styled(error.clone(), Style::default().fg(Color::Red)),
+                        "Enter: close",
+                    ),
+                    (None, None) => (
+                        Span::raw("requesting…"),
+                        "Enter: continue in the background",
+                    ),
+                    (Some(state), None) => (
+                        Span::styled(
+                            state.to_string(),
+                            Style::default().fg(project_state_color(state)),
+                        ),
+                        match state {
+                            ProjectState::Ready => "Enter: select the project",
+                            state if state.is_pending() => "Enter: continue in the background",
+                            _ => "Enter: close",
+                        },
+                    ),
+                };
+                lines.push(Line::from(vec!["State: ".bold(), status]));
+                if let Some(ProjectState::Errored(message)) = state {
+                    lines.push(Line::from(message.clone().red()));
+                }
+                lines.push(Line::default());
+                lines.push(Line::from(Span::styled(
+                    hint,
+                    Style::default().fg(Color::DarkGray),
+                )));
+                let rect = centered_rect(area, 70, lines.len() as u16 + 4);
+                f.render_widget(Clear, rect);
+                f.render_widget(
+                    Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+                        Block::default()
+                            .title(Line::from("New project".bold()))
+                            .title_alignment(Alignment::Center)
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Rounded)
+                            .border_style(Style::default().fg(Color::Yellow))
+                            .padding(Padding::uniform(1)),
+                    ),
+                    rect,
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Component, Frame};
+use crate::{action::Action, config::Config};
+
+/// Time that a notification stays visible.
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum number of notifications that are shown at once.
+const MAX_NOTIFICATIONS: usize = 3;
+
+const WIDTH: u16 = 50;
+
+struct Notification {
+    message: String,
+    is_error: bool,
+    shown_at: Instant,
+}
+
+/// Notification area in the bottom right corner.
+///
+/// It shows `Action::Notify` messages and errors for a few seconds.
+#[derive(Default)]
+pub struct Notifications {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    notifications: VecDeque<Notification>,
+}
+
+impl Notifications {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, message: String, is_error: bool) {
+        // Errors that repeat on every frame would flood the area otherwise.
+        if let Some(last) = self
+            .notifications
+            .back_mut()
+            .filter(|v| v.message == message)
+        {
+            last.shown_at = Instant::now();
+            return;
+        }
+        if self.notifications.len() == MAX_NOTIFICATIONS {
+            self.notifications.pop_front();
+        }
+        self.notifications.push_back(Notification {
+            message,
+            is_error,
+            shown_at: Instant::now(),
+        });
+    }
+}
+
+impl Component for Notifications {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Notify(message) => self.push(message, false),
+            Action::Error(message) => self.push(message, true),
+            Action::Tick | Action::Render => self
+                .notifications
+                .retain(|v| v.shown_at.elapsed() < NOTIFICATION_TIMEOUT),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let width = WIDTH.min(area.width.saturating_sub(4));
+        let mut bottom = area.bottom().saturating_sub(2);
+        for notification in self.notifications.iter().rev() {
+            let inner = width.saturating_sub(4).max(1) as usize;
+            let lines = notification.message.chars().count().div_ceil(inner);
+            let height = lines.clamp(1, 3) as u16 + 2;
+            if bottom < area.y + height {
+                break;
+            }
+            let rect = Rect::new(
+                area.right().saturating_sub(width + 2),
+                bottom - height,
+                width,
+                height,
+            );
+            let color = if notification.is_error {
+                Color::Red
+            } else {
+                Color::Green
+            };
+            f.render_widget(Clear, rect);
+            f.render_widget(
+                Paragraph::new(notification.message.as_str())
+                    .wrap(Wrap { trim: true })
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Rounded)
+                            .border_style(Style::default().fg(color))
+                            .padding(Padding::horizontal(1)),
+                    ),
+                rect,
+            );
+            bottom -= height;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{centered_rect, Component, Frame};
+use crate::{action::Action, config::Config, shuttle::DEFAULT_PROFILE};
+
+/// Popup for switching between the profiles of the configuration.
+#[derive(Default)]
+pub struct Profiles {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    active: String,
+    show_profiles: bool,
+    state: ListState,
+}
+
+impl Profiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the names of the profiles, starting with the implicit default
+    /// profile unless it is configured as well.
+    fn names(&self) -> Vec<String> {
+        let mut names = vec![DEFAULT_PROFILE.to_string()];
+        names.extend(
+            self.config
+                .profiles
+                .keys()
+                .filter(|name| name.as_str() != DEFAULT_PROFILE)
+                .cloned(),
+        );
+        names
+    }
+
+    fn select(&mut self, offset: isize) {
+        let len = self.names().len() as isize;
+        let current = self.state.selected().unwrap_or_default() as isize;
+        self.state
+            .select(Some((current + offset).rem_euclid(len) as usize));
+    }
+}
+
+impl Component for Profiles {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.show_profiles
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("Modal")
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if !self.show_profiles {
+            return Ok(None);
+        }
+        let action = match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.select(1);
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.select(-1);
+                None
+            }
+            KeyCode::Enter => {
+                self.show_profiles = false;
+                self.state
+                    .selected()
+                    .and_then(|index| self.names().get(index).cloned())
+                    .filter(|name| name != &self.active)
+                    .map(Action::SwitchProfile)
+            }
+            KeyCode::Esc => {
+                self.show_profiles = false;
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ToggleShowProfiles => {
+                self.show_profiles = !self.show_profiles;
+                let index = self.names().iter().position(|v| v == &self.active);
+                self.state.select(index.or(Some(0)));
+            }
+            Action::ProfileChanged(profile) => self.active = profile,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        if !self.show_profiles {
+            return Ok(());
+        }
+        let items = self
+            .names()
+            .into_iter()
+            .map(|name| {
+                let profile = self.config.profiles.get(&name);
+                let url = profile.and_then(|v| v.api_url.clone()).unwrap_or_default();
+                let marker = if name == self.active { "● " } else { "  " };
+                ListItem::new(Line::from(vec![
+                    Span::raw(marker),
+                    Span::styled(name, Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(format!(" {url}"), Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect::<Vec<ListItem>>();
+        let rect = centered_rect(area, 60, items.len() as u16 + 4);
+        f.render_widget(Clear, rect);
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title("Profiles")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .padding(Padding::uniform(1)),
+            )
+            .highlight_style(Style::default().fg(Color::Rgb(253, 145, 62)));
+        f.render_stateful_widget(list, rect, &mut self.state);
+        Ok(())
+    }
+}
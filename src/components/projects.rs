@@ -9,7 +9,9 @@ use tokio::sync::mpsc::UnboundedSender;
 use super::{Component, Frame};
 use crate::{
     action::Action,
+    cache::Snapshot,
     config::{Config, KeyBindings},
+    shuttle::models::{Project, ProjectState},
     tab::Tab,
 };
 
@@ -17,12 +19,81 @@ use crate::{
 pub struct Projects {
     command_tx: Option<UnboundedSender<Action>>,
     config: Config,
+    snapshot: Snapshot,
+    state: TableState,
+    focused: bool,
+    /// Project of the manifest in the current directory, to select once it is known.
+    preferred: Option<String>,
+    /// Whether the resource graph or the domains are shown over the list.
+    show_overlay: bool,
 }
 
 impl Projects {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn selected_project(&self) -> Option<&Project> {
+        self.state
+            .selected()
+            .and_then(|index| self.snapshot.state.projects.get(index))
+    }
+
+    /// Selects the preferred project once it is listed.
+    fn select_preferred(&mut self) {
+        let index = self.preferred.as_ref().and_then(|name| {
+            self.snapshot
+                .state
+                .projects
+                .iter()
+                .position(|v| &v.name == name)
+        });
+        if let Some(index) = index {
+            self.preferred = None;
+            self.state.select(Some(index));
+        }
+    }
+
+    fn select(&mut self, offset: isize) -> Option<Action> {
+        let len = self.snapshot.state.projects.len();
+        if len == 0 {
+            self.state.select(None);
+            return None;
+        }
+        let current = self.state.selected().unwrap_or_default() as isize;
+        let index = (current + offset).clamp(0, len as isize - 1) as usize;
+        self.state.select(Some(index));
+        self.selected_project()
+            .map(|project| Action::SelectProject(project.name.clone()))
+    }
+}
+
+pub fn project_state_color(state: &ProjectState) -> Color {
+    match state {
+        ProjectState::Ready => Color::Green,
+        ProjectState::Errored(_) => Color::Red,
+        ProjectState::Stopped | ProjectState::Destroyed | ProjectState::Deleted => Color::DarkGray,
+        _ => Color::Yellow,
+    }
+}
+
+/// Returns the block title that includes the freshness of the data.
+pub fn snapshot_title<'a>(title: &'a str, snapshot: &Snapshot) -> Line<'a> {
+    let mut spans = vec![Span::styled(
+        title,
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    if let Some(status) = snapshot.status() {
+        spans.push(Span::styled(
+            format!(" ({status})"),
+            Style::default().fg(if snapshot.offline {
+                Color::Red
+            } else {
+                Color::Yellow
+            }),
+        ));
+    }
+    Line::from(spans)
 }
 
 impl Component for Projects {
@@ -39,16 +110,167 @@ impl Component for Projects {
         Ok(())
     }
 
+    fn focusable(&self) -> bool {
+        !self.show_overlay
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.select(1),
+            KeyCode::Char('k') | KeyCode::Up => self.select(-1),
+            KeyCode::Enter => self.select(0).map(|_| Action::NextTab),
+            KeyCode::Char('n') if self.snapshot.is_read_only() => {
+                log::warn!("Cannot create a project while offline");
+                None
+            }
+            KeyCode::Char('n') => Some(Action::NewProject),
+            KeyCode::Char('i') => Some(Action::InitProject),
+            KeyCode::Char('g') => self
+                .selected_project()
+                .map(|project| Action::OpenGraph(project.name.clone())),
+            KeyCode::Char('d') => self
+                .selected_project()
+                .map(|project| Action::OpenDomains(project.name.clone())),
+            KeyCode::Char('s') => self
+                .selected_project()
+                .map(|project| Action::OpenSqlConsole(project.name.clone())),
+            KeyCode::Char('c') => self
+                .selected_project()
+                .map(|project| Action::OpenRequests(project.name.clone())),
+            _ => None,
+        };
+        Ok(action)
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
-            Action::Tick => {}
+            Action::PlatformUpdated(snapshot) => {
+                let selected = self.selected_project().map(|v| v.name.clone());
+                self.snapshot = *snapshot;
+                let index = selected
+                    .and_then(|name| {
+                        self.snapshot
+                            .state
+                            .projects
+                            .iter()
+                            .position(|v| v.name == name)
+                    })
+                    .unwrap_or_default();
+                self.state.select(Some(index));
+                self.select_preferred();
+                return Ok(self.select(0));
+            }
+            Action::OpenGraph(_) | Action::OpenDomains(_) => self.show_overlay = true,
+            Action::CloseGraph | Action::CloseDomains => self.show_overlay = false,
+            Action::WorkspaceDetected(workspace) => {
+                self.preferred = workspace.project_name().map(String::from);
+                self.select_preferred();
+                return Ok(self.select(0));
+            }
+            Action::SelectProject(name) => {
+                if let Some(index) = self
+                    .snapshot
+                    .state
+                    .projects
+                    .iter()
+                    .position(|v| v.name == name)
+                {
+                    self.state.select(Some(index));
+                }
+            }
             _ => {}
         }
         Ok(None)
     }
 
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
-        f.render_widget(Paragraph::new("projects"), area);
+        let rect = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        let rows = self
+            .snapshot
+            .state
+            .projects
+            .iter()
+            .map(|project| {
+                Row::new(vec![
+                    Cell::from(project.name.clone()),
+                    Cell::from(project.state.to_string())
+                        .style(Style::default().fg(project_state_color(&project.state))),
+                    Cell::from(
+                        project
+                            .idle_minutes
+                            .map(|v| v.to_string())
+                            .unwrap_or_else(|| String::from("-")),
+                    ),
+                ])
+            })
+            .collect::<Vec<Row>>();
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec!["Name", "State", "Idle minutes"])
+                    .bottom_margin(1)
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(
+                Block::default()
+                    .title(snapshot_title("Projects", &self.snapshot))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            )
+            .widths(&[
+                Constraint::Percentage(50),
+                Constraint::Percentage(30),
+                Constraint::Percentage(20),
+            ])
+            .highlight_style(Style::default().fg(if self.focused {
+                Color::Rgb(253, 145, 62)
+            } else {
+                Color::DarkGray
+            }))
+            .highlight_symbol(">> ");
+        f.render_stateful_widget(table, rect[0], &mut self.state);
+
+        let mut lines = Vec::new();
+        if let Some(project) = self.selected_project() {
+            if let ProjectState::Errored(message) = &project.state {
+                lines.push(Line::from(vec![
+                    "Error: ".red().bold(),
+                    Span::raw(message.clone()),
+                ]));
+                lines.push(Line::default());
+            }
+            if let Some(service) = self.snapshot.state.services.get(&project.name) {
+                lines.push(Line::from(vec![
+                    "URI: ".bold(),
+                    Span::raw(service.uri.clone()),
+                ]));
+                lines.push(Line::default());
+            }
+            lines.push(Line::from("Resources".bold()));
+            let resources = self.snapshot.state.resources(&project.name);
+            if resources.is_empty() {
+                lines.push(Line::from("No resources".italic()));
+            }
+            for resource in resources {
+                lines.push(Line::from(format!(" • {}", resource.r#type)));
+            }
+        }
+        f.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(Line::from("Details".bold()))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .padding(Padding::horizontal(1)),
+            ),
+            rect[1],
+        );
         Ok(())
     }
 }
@@ -0,0 +1,494 @@
+use std::collections::HashMap;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use super::{editor::Editor, Component, Frame};
+use crate::{
+    action::Action,
+    config::Config,
+    request::{format_headers, parse_headers, HttpRequest, HttpResponse, SavedRequests, METHODS},
+};
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    #[default]
+    Saved,
+    Name,
+    Method,
+    Path,
+    Headers,
+    Body,
+    Response,
+}
+
+const FIELDS: &[Field] = &[
+    Field::Saved,
+    Field::Name,
+    Field::Method,
+    Field::Path,
+    Field::Headers,
+    Field::Body,
+    Field::Response,
+];
+
+/// Popup that sends requests to the service of a project and keeps the
+/// saved ones per project.
+#[derive(Default)]
+pub struct RequestRunner {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// Project of the open runner.
+    project: Option<String>,
+    saved: SavedRequests,
+    /// Selected entry of the list, where the first one is a new request.
+    state: ListState,
+    field: Field,
+    name: Input,
+    method: usize,
+    path: Input,
+    headers: Editor,
+    body: Editor,
+    /// Responses by request name, which includes the ones that are still pending.
+    responses: HashMap<String, Option<Result<HttpResponse, String>>>,
+    scroll: u16,
+    error: Option<String>,
+}
+
+impl RequestRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn open(&mut self, project: String) {
+        *self = Self {
+            command_tx: self.command_tx.take(),
+            config: std::mem::take(&mut self.config),
+            saved: SavedRequests::load(&project),
+            project: Some(project),
+            state: ListState::default().with_selected(Some(0)),
+            path: Input::new(String::from("/")),
+            ..Default::default()
+        };
+    }
+
+    /// Fills in the fields with a saved request, or clears them.
+    fn load(&mut self, request: Option<HttpRequest>) {
+        let request = request.unwrap_or_else(|| HttpRequest {
+            path: String::from("/"),
+            ..Default::default()
+        });
+        self.name = Input::new(request.name);
+        self.method = METHODS
+            .iter()
+            .position(|v| v.eq_ignore_ascii_case(&request.method))
+            .unwrap_or_default();
+        self.path = Input::new(request.path);
+        self.headers = Editor::new(&format_headers(&request.headers));
+        self.body = Editor::new(&request.body);
+        self.scroll = 0;
+        self.error = None;
+    }
+
+    /// Returns the request of the fields, named after its method and path
+    /// if it has no name.
+    fn request(&self) -> Result<HttpRequest, String> {
+        let method = METHODS[self.method].to_string();
+        let path = self.path.value().trim().to_string();
+        let name = match self.name.value().trim() {
+            "" => format!("{method} {path}"),
+            name => name.to_string(),
+        };
+        Ok(HttpRequest {
+            name,
+            method,
+            path,
+            headers: parse_headers(&self.headers.text())?,
+            body: self.body.text(),
+        })
+    }
+
+    fn send(&mut self, request: HttpRequest) -> Option<Action> {
+        let project = self.project.clone()?;
+        self.responses.insert(request.name.clone(), None);
+        self.scroll = 0;
+        Some(Action::SendRequest(project, Box::new(request)))
+    }
+
+    fn save(&mut self) {
+        let result = self.request().and_then(|request| {
+            let name = request.name.clone();
+            self.saved.save(request).map_err(|e| e.to_string())?;
+            Ok(name)
+        });
+        match result {
+            Ok(name) => {
+                self.error = None;
+                let index = self.saved.requests.iter().position(|v| v.name == name);
+                self.state.select(index.map(|v| v + 1));
+                self.name = Input::new(name);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    fn handle_saved_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let len = self.saved.requests.len() + 1;
+        let selected = self.state.selected().unwrap_or_default();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.state.select(Some((selected + 1).min(len - 1)));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Enter => {
+                let request = selected
+                    .checked_sub(1)
+                    .and_then(|index| self.saved.requests.get(index))
+                    .cloned();
+                self.load(request);
+                self.field = Field::Name;
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(request) = selected
+                    .checked_sub(1)
+                    .and_then(|index| self.saved.requests.get(index))
+                {
+                    let name = request.name.clone();
+                    if let Err(e) = self.saved.remove(&name) {
+                        self.error = Some(e.to_string());
+                    }
+                    self.state.select(Some(selected.min(len - 2)));
+                }
+            }
+            KeyCode::Char('a') => {
+                // Replays every saved request, e.g. after a deploy.
+                for request in self.saved.requests.clone() {
+                    if let (Some(action), Some(tx)) = (self.send(request), &self.command_tx) {
+                        let _ = tx.send(action);
+                    }
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn response_lines(&self) -> Vec<Line<'static>> {
+        let name = match self.request() {
+            Ok(request) => request.name,
+            Err(_) => return Vec::new(),
+        };
+        match self.responses.get(&name) {
+            None => vec![Line::from("Ctrl-r sends the request".italic())],
+            Some(None) => vec![Line::from("Sending…".italic())],
+            Some(Some(Err(e))) => vec![Line::from(e.clone().red())],
+            Some(Some(Ok(response))) => {
+                let color = if response.is_success() {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+                let mut lines = vec![
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{} {}", response.status, response.reason),
+                            Style::default().fg(color).bold(),
+                        ),
+                        Span::styled(
+                            format!(
+                                " · {:.0?} · {} bytes",
+                                response.elapsed,
+                                response.body.len()
+                            ),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ]),
+                    Line::default(),
+                ];
+                lines.extend(response.headers.iter().map(|(name, value)| {
+                    Line::from(vec![
+                        Span::styled(format!("{name}: "), Style::default().fg(Color::DarkGray)),
+                        Span::raw(value.clone()),
+                    ])
+                }));
+                lines.push(Line::default());
+                lines.extend(
+                    response
+                        .pretty_body()
+                        .lines()
+                        .map(|line| Line::from(line.to_string())),
+                );
+                lines
+            }
+        }
+    }
+
+    fn status_span(&self, name: &str) -> Span<'static> {
+        match self.responses.get(name) {
+            Some(Some(Ok(response))) => Span::styled(
+                format!("{} ", response.status),
+                Style::default().fg(if response.is_success() {
+                    Color::Green
+                } else {
+                    Color::Red
+                }),
+            ),
+            Some(Some(Err(_))) => Span::styled("ERR ", Style::default().fg(Color::Red)),
+            Some(None) => Span::styled("… ", Style::default().fg(Color::DarkGray)),
+            None => Span::raw(""),
+        }
+    }
+}
+
+impl Component for RequestRunner {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.project.is_some()
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("Modal")
+    }
+
+    fn captures_input(&self) -> bool {
+        self.project.is_some()
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.project.is_none() {
+            return Ok(None);
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let action = match (key.code, ctrl) {
+            (KeyCode::Esc, _) => Some(Action::CloseRequests),
+            (KeyCode::Char('r'), true) => match self.request() {
+                Ok(request) => {
+                    self.error = None;
+                    self.send(request)
+                }
+                Err(e) => {
+                    self.error = Some(e);
+                    None
+                }
+            },
+            (KeyCode::Char('s'), true) => {
+                self.save();
+                None
+            }
+            (KeyCode::Tab, _) | (KeyCode::BackTab, _) => {
+                let index = FIELDS.iter().position(|v| *v == self.field).unwrap_or(0);
+                let offset = if key.code == KeyCode::Tab {
+                    1
+                } else {
+                    FIELDS.len() - 1
+                };
+                self.field = FIELDS[(index + offset) % FIELDS.len()];
+                None
+            }
+            _ => match self.field {
+                Field::Saved => self.handle_saved_key(key),
+                Field::Name => {
+                    self.name.handle_event(&CrosstermEvent::Key(key));
+                    None
+                }
+                Field::Path => {
+                    self.path.handle_event(&CrosstermEvent::Key(key));
+                    None
+                }
+                Field::Method => {
+                    match key.code {
+                        KeyCode::Right | KeyCode::Char(' ') | KeyCode::Char('l') => {
+                            self.method = (self.method + 1) % METHODS.len();
+                        }
+                        KeyCode::Left | KeyCode::Char('h') => {
+                            self.method = (self.method + METHODS.len() - 1) % METHODS.len();
+                        }
+                        _ => {}
+                    }
+                    None
+                }
+                Field::Headers => {
+                    self.headers.handle_key_events(key);
+                    None
+                }
+                Field::Body => {
+                    self.body.handle_key_events(key);
+                    None
+                }
+                Field::Response => {
+                    match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            self.scroll = self.scroll.saturating_add(1)
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            self.scroll = self.scroll.saturating_sub(1)
+                        }
+                        KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                        KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                        _ => {}
+                    }
+                    None
+                }
+            },
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::OpenRequests(project) => self.open(project),
+            Action::CloseRequests => self.project = None,
+            Action::RequestCompleted(name, response) => {
+                self.responses.insert(name, Some(Ok(*response)));
+            }
+            Action::RequestFailed(name, e) => {
+                self.responses.insert(name, Some(Err(e)));
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let Some(project) = self.project.clone() else {
+            return Ok(());
+        };
+        let rect = area.inner(&Margin {
+            horizontal: 4,
+            vertical: 2,
+        });
+        f.render_widget(Clear, rect);
+        let outer = Block::default()
+            .title(Line::from(format!("Requests to {project}").bold()))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = outer.inner(rect);
+        f.render_widget(outer, rect);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(25), Constraint::Percentage(75)])
+            .split(rows[0]);
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(5),
+                Constraint::Length(7),
+                Constraint::Min(3),
+            ])
+            .split(columns[1]);
+        let field = self.field;
+        let block = |title: &'static str, current: Field| {
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if field == current {
+                    Color::Rgb(253, 145, 62)
+                } else {
+                    Color::DarkGray
+                }))
+        };
+
+        let items = std::iter::once(ListItem::new("+ New request".italic()))
+            .chain(self.saved.requests.iter().map(|request| {
+                ListItem::new(Line::from(vec![
+                    self.status_span(&request.name),
+                    Span::raw(request.name.clone()),
+                ]))
+            }))
+            .collect::<Vec<_>>();
+        f.render_stateful_widget(
+            List::new(items)
+                .block(block("Saved", Field::Saved))
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+            columns[0],
+            &mut self.state,
+        );
+
+        let inputs = [
+            (&self.name, "Name", Field::Name, right[0]),
+            (&self.path, "Path", Field::Path, right[1]),
+        ];
+        for (input, title, current, area) in inputs {
+            let area = if current == Field::Path {
+                let parts = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Length(11), Constraint::Min(1)])
+                    .split(area);
+                f.render_widget(
+                    Paragraph::new(Line::from(vec![
+                        Span::raw("‹ "),
+                        Span::raw(METHODS[self.method]).bold(),
+                        Span::raw(" ›"),
+                    ]))
+                    .block(block("Method", Field::Method)),
+                    parts[0],
+                );
+                parts[1]
+            } else {
+                area
+            };
+            let block = block(title, current);
+            let inner = block.inner(area);
+            f.render_widget(Paragraph::new(input.value()).block(block), area);
+            if field == current {
+                f.set_cursor(inner.x + input.visual_cursor() as u16, inner.y);
+            }
+        }
+        self.headers.draw(
+            f,
+            right[2],
+            block("Headers (Name: value)", Field::Headers),
+            field == Field::Headers,
+        );
+        self.body.draw(
+            f,
+            right[3],
+            block("Body", Field::Body),
+            field == Field::Body,
+        );
+        f.render_widget(
+            Paragraph::new(self.response_lines())
+                .scroll((self.scroll, 0))
+                .block(block("Response", Field::Response)),
+            right[4],
+        );
+
+        let status = match &self.error {
+            Some(e) => Span::styled(format!("{e}  "), Style::default().fg(Color::Red)),
+            None => Span::raw(""),
+        };
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                status,
+                Span::styled(
+                    "Ctrl-r: send  Ctrl-s: save  a: replay saved  d: delete  Tab: switch  Esc: close",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])),
+            rows[1],
+        );
+        Ok(())
+    }
+}
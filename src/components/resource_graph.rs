@@ -0,0 +1,317 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{
+    deployment_details::format_timestamp, deployments::deployment_state_color, titled_block,
+    Component, Frame,
+};
+use crate::{
+    action::Action,
+    cache::Snapshot,
+    config::Config,
+    diff::secret_names,
+    graph::{self, resource_kind, Node},
+    tab::Tab,
+};
+
+/// Full-screen page with the graph of the deployments and resources of a project.
+#[derive(Default)]
+pub struct ResourceGraph {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    snapshot: Snapshot,
+    /// Project of the open graph.
+    project: Option<String>,
+    nodes: Vec<Node>,
+    state: ListState,
+}
+
+impl ResourceGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn rebuild(&mut self) {
+        if let Some(project) = &self.project {
+            self.nodes = graph::build(&self.snapshot.state, project);
+            let selected = self.state.selected().unwrap_or_default();
+            self.state
+                .select(Some(selected.min(self.nodes.len().saturating_sub(1))));
+        }
+    }
+
+    fn select(&mut self, offset: isize) {
+        let len = self.nodes.len() as isize;
+        let current = self.state.selected().unwrap_or_default() as isize;
+        self.state
+            .select(Some((current + offset).clamp(0, (len - 1).max(0)) as usize));
+    }
+
+    fn selected(&self) -> Option<&Node> {
+        self.state
+            .selected()
+            .and_then(|index| self.nodes.get(index))
+    }
+
+    /// Returns the action that shows the details of the selected node.
+    fn jump(&self) -> Option<Action> {
+        let project = self.project.clone()?;
+        match self.selected()? {
+            Node::Service { .. } => Some(Action::GoToTab(Tab::Deployments)),
+            Node::Deployment { deployment, .. } => {
+                if let Some(tx) = &self.command_tx {
+                    let _ = tx.send(Action::GoToTab(Tab::Deployments));
+                }
+                Some(Action::OpenDeployment(project, deployment.id.clone()))
+            }
+            Node::Resource { resource, .. } if resource_kind(&resource.r#type) == "database" => {
+                Some(Action::OpenSqlConsole(project))
+            }
+            // The details of other resources are shown next to the graph.
+            Node::Resource { .. } => None,
+        }
+    }
+
+    fn line(node: &Node) -> Line<'static> {
+        let edge = |provisioned: bool| {
+            Style::default().fg(if provisioned {
+                Color::Green
+            } else {
+                Color::DarkGray
+            })
+        };
+        match node {
+            Node::Service { name, .. } => Line::from(vec![
+                Span::raw("◆ "),
+                Span::styled(name.clone(), Style::default().add_modifier(Modifier::BOLD)),
+            ]),
+            Node::Deployment {
+                deployment,
+                current,
+                last,
+            } => {
+                let color = deployment_state_color(deployment.state);
+                Line::from(vec![
+                    Span::styled(
+                        if *last { "└─▶ " } else { "├─▶ " },
+                        Style::default().fg(color),
+                    ),
+                    Span::raw(deployment.id.get(..7).unwrap_or(&deployment.id).to_string()),
+                    Span::styled(format!(" {}", deployment.state), Style::default().fg(color)),
+                    Span::styled(
+                        if *current { " (current)" } else { "" },
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+            }
+            Node::Resource {
+                resource,
+                provisioned,
+                last,
+                last_deployment,
+            } => Line::from(vec![
+                Span::styled(
+                    if *last_deployment { "    " } else { "│   " },
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!(
+                        "{}── {} ",
+                        if *last { "└" } else { "├" },
+                        if *provisioned { "●" } else { "○" }
+                    ),
+                    edge(*provisioned),
+                ),
+                Span::raw(resource.r#type.clone()),
+            ]),
+        }
+    }
+
+    fn details(&self) -> Vec<Line<'static>> {
+        let field = |name: &'static str, value: String| {
+            Line::from(vec![Span::from(name).bold(), Span::raw(value)])
+        };
+        let mut lines = Vec::new();
+        match self.selected() {
+            None => lines.push(Line::from("No service found".italic())),
+            Some(Node::Service { name, uri }) => {
+                lines.push(field("Service  ", name.clone()));
+                lines.push(field(
+                    "URI      ",
+                    uri.clone().unwrap_or_else(|| "-".into()),
+                ));
+                lines.push(Line::default());
+                lines.push(Line::from("enter: deployments".dark_gray()));
+            }
+            Some(Node::Deployment {
+                deployment,
+                current,
+                ..
+            }) => {
+                lines.push(field("Deployment  ", deployment.id.clone()));
+                lines.push(Line::from(vec![
+                    "State       ".bold(),
+                    Span::styled(
+                        deployment.state.to_string(),
+                        Style::default().fg(deployment_state_color(deployment.state)),
+                    ),
+                    Span::raw(if *current { " (current)" } else { "" }),
+                ]));
+                lines.push(field(
+                    "Updated     ",
+                    format_timestamp(deployment.last_update),
+                ));
+                if let Some(branch) = &deployment.git_branch {
+                    lines.push(field("Branch      ", branch.clone()));
+                }
+                if let Some(message) = &deployment.git_commit_msg {
+                    lines.push(field("Commit      ", message.clone()));
+                }
+                lines.push(Line::default());
+                lines.push(Line::from("enter: deployment details".dark_gray()));
+            }
+            Some(Node::Resource {
+                resource,
+                provisioned,
+                ..
+            }) => {
+                lines.push(field("Type         ", resource.r#type.clone()));
+                lines.push(field(
+                    "Kind         ",
+                    resource_kind(&resource.r#type).into(),
+                ));
+                lines.push(Line::from(vec![
+                    "Provisioned  ".bold(),
+                    if *provisioned {
+                        "yes".green()
+                    } else {
+                        "no".dark_gray()
+                    },
+                ]));
+                if resource_kind(&resource.r#type) == "database" {
+                    lines.push(Line::default());
+                    lines.push(Line::from("enter: SQL console".dark_gray()));
+                }
+                if resource.r#type == "secrets" {
+                    let names = secret_names(std::slice::from_ref(resource));
+                    lines.push(field(
+                        "Secrets      ",
+                        names.into_iter().collect::<Vec<_>>().join(", "),
+                    ));
+                } else {
+                    let masked = resource.masked();
+                    for (name, value) in [("Config", &masked.config), ("Data", &masked.data)] {
+                        if value.is_null() {
+                            continue;
+                        }
+                        lines.push(Line::default());
+                        lines.push(Line::from(Span::from(name).bold()));
+                        let json = serde_json::to_string_pretty(value).unwrap_or_default();
+                        lines.extend(json.lines().map(|line| Line::from(line.to_string())));
+                    }
+                }
+            }
+        }
+        lines
+    }
+}
+
+impl Component for ResourceGraph {
+    fn assigned_tab(&self) -> Option<Tab> {
+        Some(Tab::Projects)
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.project.is_some()
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("ResourceGraph")
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.project.is_none() {
+            return Ok(None);
+        }
+        let action = match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.select(1);
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.select(-1);
+                None
+            }
+            KeyCode::Enter => self.jump(),
+            KeyCode::Esc => Some(Action::CloseGraph),
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::PlatformUpdated(snapshot) => {
+                self.snapshot = *snapshot;
+                self.rebuild();
+            }
+            Action::OpenGraph(project) => {
+                self.project = Some(project);
+                self.state.select(Some(0));
+                self.rebuild();
+            }
+            Action::CloseGraph => {
+                self.project = None;
+                self.nodes.clear();
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let Some(project) = self.project.clone() else {
+            return Ok(());
+        };
+        f.render_widget(Clear, area);
+        let title = format!("Resources of {project}");
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(area);
+        let items = self
+            .nodes
+            .iter()
+            .map(|node| ListItem::new(Self::line(node)))
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(
+                titled_block(&title).title(
+                    block::Title::from("● provisioned ○ released  esc: back")
+                        .alignment(Alignment::Right)
+                        .position(block::Position::Bottom),
+                ),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        f.render_stateful_widget(list, columns[0], &mut self.state);
+        f.render_widget(
+            Paragraph::new(self.details())
+                .wrap(Wrap { trim: false })
+                .block(titled_block("Details")),
+            columns[1],
+        );
+        Ok(())
+    }
+}
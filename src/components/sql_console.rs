@@ -0,0 +1,407 @@
+use std::fs;
+
+use chrono::Local;
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{editor::Editor, Component, Frame};
+use crate::{
+    action::Action,
+    config::Config,
+    sql::{History, QueryResult},
+};
+
+/// Widest that a column of the result grid gets.
+const MAX_COLUMN_WIDTH: usize = 40;
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    #[default]
+    Editor,
+    Results,
+    History,
+}
+
+#[derive(Default, Clone, PartialEq, Eq)]
+enum Status {
+    #[default]
+    Connecting,
+    Ready,
+    Running,
+    Failed(String),
+}
+
+/// Popup with a query editor and the results of the queries on a database.
+#[derive(Default)]
+pub struct SqlConsole {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// Project or URL of the open console.
+    target: Option<String>,
+    database: Option<String>,
+    status: Status,
+    pane: Pane,
+    editor: Editor,
+    result: Option<QueryResult>,
+    results_state: TableState,
+    /// First column of the result grid that is shown.
+    column: usize,
+    history: History,
+    history_state: ListState,
+    /// Position in the history while browsing it from the editor.
+    history_index: Option<usize>,
+}
+
+impl SqlConsole {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn open(&mut self, target: String) {
+        *self = Self {
+            command_tx: self.command_tx.take(),
+            config: std::mem::take(&mut self.config),
+            target: Some(target),
+            history: History::load(),
+            ..Default::default()
+        };
+    }
+
+    fn run(&mut self) -> Option<Action> {
+        let query = self.editor.text().trim().to_string();
+        if query.is_empty() || matches!(self.status, Status::Connecting | Status::Running) {
+            return None;
+        }
+        if let Err(e) = self.history.push(&query) {
+            log::error!("Failed to save the query history: {e}");
+        }
+        self.history_index = None;
+        self.status = Status::Running;
+        Some(Action::RunQuery(query))
+    }
+
+    /// Replaces the query with an older (`offset < 0`) or newer one of the history.
+    fn browse_history(&mut self, offset: isize) {
+        let len = self.history.queries.len() as isize;
+        if len == 0 {
+            return;
+        }
+        let current = self.history_index.map_or(len, |v| v as isize);
+        let index = current + offset;
+        if index >= len {
+            self.history_index = None;
+            self.editor = Editor::default();
+        } else {
+            let index = index.max(0) as usize;
+            self.history_index = Some(index);
+            self.editor = Editor::new(&self.history.queries[index]);
+        }
+    }
+
+    /// Writes the result to a CSV file in the current directory.
+    fn export(&mut self) {
+        let Some(result) = &self.result else {
+            return;
+        };
+        let name = match self.target.as_deref() {
+            Some(target) if !target.contains(':') => target,
+            _ => "database",
+        };
+        let path = format!("{name}-query-{}.csv", Local::now().format("%Y%m%d-%H%M%S"));
+        let message = match fs::write(&path, result.to_csv()) {
+            Ok(()) => format!("Exported {} rows to {path}", result.rows.len()),
+            Err(e) => {
+                log::error!("Failed to write {path}: {e}");
+                format!("Failed to write {path}: {e}")
+            }
+        };
+        if let Some(tx) = &self.command_tx {
+            let _ = tx.send(Action::Notify(message));
+        }
+    }
+
+    fn handle_results_key(&mut self, key: KeyEvent) {
+        let len = self.result.as_ref().map_or(0, |v| v.rows.len());
+        let columns = self.result.as_ref().map_or(0, |v| v.columns.len());
+        let selected = self.results_state.selected().unwrap_or_default();
+        let row = match key.code {
+            KeyCode::Char('j') | KeyCode::Down => selected + 1,
+            KeyCode::Char('k') | KeyCode::Up => selected.saturating_sub(1),
+            KeyCode::PageDown => selected + 10,
+            KeyCode::PageUp => selected.saturating_sub(10),
+            KeyCode::Char('g') | KeyCode::Home => 0,
+            KeyCode::Char('G') | KeyCode::End => len,
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.column = (self.column + 1).min(columns.saturating_sub(1));
+                selected
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.column = self.column.saturating_sub(1);
+                selected
+            }
+            _ => selected,
+        };
+        self.results_state
+            .select(Some(row.min(len.saturating_sub(1))));
+    }
+
+    fn handle_history_key(&mut self, key: KeyEvent) {
+        let len = self.history.queries.len();
+        let selected = self.history_state.selected().unwrap_or_default();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.history_state
+                    .select(Some((selected + 1).min(len.saturating_sub(1))));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.history_state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Enter => {
+                // The list shows the most recent query first.
+                if let Some(query) = self.history.queries.iter().rev().nth(selected) {
+                    self.editor = Editor::new(query);
+                    self.pane = Pane::Editor;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn draw_results(&mut self, f: &mut Frame<'_>, area: Rect, block: Block<'_>) {
+        let Some(result) = &self.result else {
+            let message = match &self.status {
+                Status::Connecting => "Connecting…".italic(),
+                Status::Failed(_) => "".into(),
+                _ => "Ctrl-r runs the query".italic(),
+            };
+            f.render_widget(Paragraph::new(message).block(block), area);
+            return;
+        };
+        if result.columns.is_empty() {
+            let message = format!("{} rows affected", result.affected.unwrap_or_default());
+            f.render_widget(Paragraph::new(message).block(block), area);
+            return;
+        }
+        let column = self.column.min(result.columns.len() - 1);
+        let widths = result
+            .columns
+            .iter()
+            .enumerate()
+            .skip(column)
+            .map(|(i, name)| {
+                let width = result
+                    .rows
+                    .iter()
+                    .map(|row| row[i].as_deref().map_or(4, |v| v.chars().count()))
+                    .chain([name.chars().count()])
+                    .max()
+                    .unwrap_or_default();
+                Constraint::Length(width.min(MAX_COLUMN_WIDTH) as u16)
+            })
+            .collect::<Vec<_>>();
+        let rows = result.rows.iter().map(|row| {
+            Row::new(row.iter().skip(column).map(|value| match value {
+                Some(value) => Cell::from(value.lines().next().unwrap_or_default().to_string()),
+                None => Cell::from("NULL".dark_gray()),
+            }))
+        });
+        let table = Table::new(rows)
+            .header(
+                Row::new(result.columns.iter().skip(column).cloned())
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .widths(&widths)
+            .column_spacing(2)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        f.render_stateful_widget(table, area, &mut self.results_state);
+    }
+}
+
+impl Component for SqlConsole {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.target.is_some()
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("Modal")
+    }
+
+    fn captures_input(&self) -> bool {
+        self.target.is_some()
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.target.is_none() {
+            return Ok(None);
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let action = match (key.code, ctrl) {
+            (KeyCode::Esc, _) => Some(Action::CloseSqlConsole),
+            (KeyCode::Char('r'), true) | (KeyCode::F(5), _) => self.run(),
+            (KeyCode::Char('s'), true) => {
+                self.export();
+                None
+            }
+            (KeyCode::Char('p'), true) => {
+                self.browse_history(-1);
+                None
+            }
+            (KeyCode::Char('n'), true) => {
+                self.browse_history(1);
+                None
+            }
+            (KeyCode::Tab, _) | (KeyCode::BackTab, _) => {
+                let panes = [Pane::Editor, Pane::Results, Pane::History];
+                let index = panes
+                    .iter()
+                    .position(|v| *v == self.pane)
+                    .unwrap_or_default();
+                let offset = if key.code == KeyCode::Tab { 1 } else { 2 };
+                self.pane = panes[(index + offset) % panes.len()];
+                None
+            }
+            _ => {
+                match self.pane {
+                    Pane::Editor => {
+                        self.editor.handle_key_events(key);
+                    }
+                    Pane::Results => self.handle_results_key(key),
+                    Pane::History => self.handle_history_key(key),
+                }
+                None
+            }
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::OpenSqlConsole(target) => self.open(target),
+            Action::CloseSqlConsole => self.target = None,
+            Action::SqlConnected(database) if self.target.is_some() => {
+                self.database = Some(database);
+                self.status = Status::Ready;
+            }
+            Action::QueryFinished(result) if self.target.is_some() => {
+                self.result = Some(*result);
+                self.results_state.select(Some(0));
+                self.column = 0;
+                self.status = Status::Ready;
+            }
+            Action::QueryFailed(e) if self.target.is_some() => {
+                self.status = Status::Failed(e);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let Some(target) = self.target.clone() else {
+            return Ok(());
+        };
+        let rect = area.inner(&Margin {
+            horizontal: 4,
+            vertical: 2,
+        });
+        f.render_widget(Clear, rect);
+        let title = match &self.database {
+            Some(database) => format!("SQL console: {target} ({database})"),
+            None => format!("SQL console: {target}"),
+        };
+        let outer = Block::default()
+            .title(Line::from(title.bold()))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = outer.inner(rect);
+        f.render_widget(outer, rect);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(10),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(rows[0]);
+        let pane = self.pane;
+        let block = |title: &'static str, focused: bool| {
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if focused {
+                    Color::Rgb(253, 145, 62)
+                } else {
+                    Color::DarkGray
+                }))
+        };
+
+        self.editor.draw(
+            f,
+            top[0],
+            block("Query", pane == Pane::Editor),
+            pane == Pane::Editor,
+        );
+        let items = self
+            .history
+            .queries
+            .iter()
+            .rev()
+            .map(|query| ListItem::new(query.replace('\n', " ")))
+            .collect::<Vec<_>>();
+        f.render_stateful_widget(
+            List::new(items)
+                .block(block("History", pane == Pane::History))
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+            top[1],
+            &mut self.history_state,
+        );
+        self.draw_results(f, rows[1], block("Results", pane == Pane::Results));
+
+        let status = match &self.status {
+            Status::Connecting => Span::raw("Connecting"),
+            Status::Running => Span::raw("Running"),
+            Status::Failed(e) => Span::styled(e.clone(), Style::default().fg(Color::Red)),
+            Status::Ready => match &self.result {
+                Some(result) if result.columns.is_empty() => {
+                    Span::raw(format!("Done in {:.2?}", result.elapsed))
+                }
+                Some(result) => Span::raw(format!(
+                    "{} rows in {:.2?}",
+                    result.rows.len(),
+                    result.elapsed
+                )),
+                None => Span::raw("Connected"),
+            },
+        };
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                status,
+                Span::styled(
+                    "  Ctrl-r: run  Ctrl-p/n: history  Ctrl-s: export CSV  Tab: switch  Esc: close",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])),
+            rows[2],
+        );
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ use ratatui::{
     prelude::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style, Stylize},
     symbols::DOT,
-    text::Line,
+    text::{Line, Span},
     widgets::{block::Position, Block, Borders, Padding, Tabs as TuiTabs},
 };
 use serde::{Deserialize, Serialize};
@@ -16,13 +16,72 @@ use tokio::sync::mpsc::UnboundedSender;
 use super::{Component, Frame};
 use crate::{
     action::Action,
+    cache::{PlatformState, Snapshot},
     config::{Config, KeyBindings},
+    probe,
+    shuttle::models::DeploymentState,
     tab::Tab,
 };
 
+/// States of the deployments that are counted on the Deployments tab, in order.
+const IN_PROGRESS: [DeploymentState; 4] = [
+    DeploymentState::Queued,
+    DeploymentState::Building,
+    DeploymentState::Built,
+    DeploymentState::Loading,
+];
+
+/// Returns the badge of a tab, which reflects the state of the platform.
+///
+/// `healthy` has the result of the last probe of each running service.
+fn badge(
+    tab: Tab,
+    state: &PlatformState,
+    healthy: &HashMap<String, bool>,
+) -> Option<Span<'static>> {
+    match tab {
+        Tab::Projects => state
+            .projects
+            .iter()
+            .any(|project| project.state.is_errored())
+            .then(|| Span::styled(" ●", Style::default().fg(Color::Red))),
+        Tab::Deployments => {
+            let counts = IN_PROGRESS
+                .iter()
+                .filter_map(|target| {
+                    let count = state
+                        .deployments
+                        .values()
+                        .flatten()
+                        .filter(|deployment| &deployment.state == target)
+                        .count();
+                    (count > 0).then(|| format!("{count} {target}"))
+                })
+                .collect::<Vec<_>>();
+            (!counts.is_empty()).then(|| {
+                Span::styled(
+                    format!(" ({})", counts.join(", ")),
+                    Style::default().fg(Color::Yellow),
+                )
+            })
+        }
+        Tab::Dashboard => {
+            let down = healthy.values().filter(|healthy| !**healthy).count();
+            (down > 0)
+                .then(|| Span::styled(format!(" ({down} down)"), Style::default().fg(Color::Red)))
+        }
+        _ => None,
+    }
+}
+
 #[derive(Default)]
 pub struct Tabs {
     tab: Tab,
+    profile: String,
+    processing: bool,
+    snapshot: Snapshot,
+    /// Result of the last probe of each running service, by project.
+    healthy: HashMap<String, bool>,
     command_tx: Option<UnboundedSender<Action>>,
     config: Config,
 }
@@ -47,11 +106,21 @@ impl Component for Tabs {
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Tick => {}
-            Action::NextTab => {
-                self.tab = next_cycle(&self.tab).unwrap_or_default();
+            // The tab that a view-only tab was opened from stays highlighted.
+            Action::TabChanged(tab) if !tab.is_view_only() => self.tab = tab,
+            Action::ProfileChanged(profile) => self.profile = profile,
+            Action::EnterProcessing => self.processing = true,
+            Action::ExitProcessing => self.processing = false,
+            Action::PlatformUpdated(snapshot) => {
+                self.snapshot = *snapshot;
+                let running = probe::targets(&self.snapshot.state)
+                    .into_iter()
+                    .map(|(project, _, _)| project)
+                    .collect::<Vec<_>>();
+                self.healthy.retain(|project, _| running.contains(project));
             }
-            Action::PreviousTab => {
-                self.tab = previous_cycle(&self.tab).unwrap_or_default();
+            Action::ServiceProbed(probe) => {
+                self.healthy.insert(probe.project, probe.check.is_healthy());
             }
             _ => {}
         }
@@ -63,15 +132,23 @@ impl Component for Tabs {
             .constraints(vec![Constraint::Percentage(100)])
             .margin(1)
             .split(area);
-        let modes = all::<Tab>().collect::<Vec<_>>();
+        let modes = Tab::tabs().collect::<Vec<_>>();
         let titles = modes
             .iter()
             .cloned()
-            .map(|v| Line::from(v.to_string()))
+            .map(|v| {
+                let mut spans = vec![Span::raw(v.to_string())];
+                spans.extend(badge(v, &self.snapshot.state, &self.healthy));
+                Line::from(spans)
+            })
             .collect::<Vec<Line>>();
         f.render_widget(
             Block::default()
-                .title("Shuttle TUI")
+                .title(format!(
+                    "Shuttle TUI · {}{}",
+                    self.profile,
+                    if self.processing { " (working…)" } else { "" }
+                ))
                 .title_position(Position::Top)
                 .title_alignment(Alignment::Center)
                 .title_style(Style::default().bold())
@@ -106,3 +183,71 @@ impl Component for Tabs {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::shuttle::models::{Deployment, Project, ProjectState};
+
+    #[test]
+    fn test_badges() {
+        let deployment = |state| Deployment {
+            id: String::new(),
+            service_id: String::new(),
+            state,
+            last_update: Utc::now(),
+            git_commit_id: None,
+            git_commit_msg: None,
+            git_branch: None,
+            git_dirty: None,
+        };
+        let mut state = PlatformState {
+            projects: vec![Project {
+                id: String::new(),
+                name: String::from("api"),
+                state: ProjectState::Ready,
+                idle_minutes: None,
+            }],
+            deployments: [(
+                String::from("api"),
+                vec![
+                    deployment(DeploymentState::Building),
+                    deployment(DeploymentState::Queued),
+                    deployment(DeploymentState::Building),
+                    deployment(DeploymentState::Running),
+                ],
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let healthy = HashMap::from([(String::from("api"), false)]);
+        let content = |tab| badge(tab, &state, &healthy).map(|v| v.content.to_string());
+        assert_eq!(content(Tab::Projects), None);
+        assert_eq!(
+            content(Tab::Deployments).as_deref(),
+            Some(" (1 queued, 2 building)")
+        );
+        assert_eq!(content(Tab::Dashboard).as_deref(), Some(" (1 down)"));
+        assert_eq!(content(Tab::Home), None);
+        state.projects[0].state = ProjectState::Errored(String::from("boom"));
+        assert_eq!(
+            badge(Tab::Projects, &state, &healthy).map(|v| v.style.fg),
+            Some(Some(Color::Red))
+        );
+    }
+
+    #[test]
+    fn test_tab_changed() -> Result<()> {
+        let mut tabs = Tabs::new();
+        tabs.update(Action::TabChanged(Tab::Deployments))?;
+        assert_eq!(tabs.tab, Tab::Deployments);
+        tabs.update(Action::TabChanged(Tab::Logs))?;
+        assert_eq!(tabs.tab, Tab::Deployments);
+        tabs.update(Action::NextTab)?;
+        assert_eq!(tabs.tab, Tab::Deployments);
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{centered_rect, Component, Frame};
+use crate::{
+    action::Action,
+    config::Config,
+    task::{TaskInfo, TaskManager},
+};
+
+/// Popup for listing and cancelling the running tasks.
+///
+/// It also asks for confirmation when quitting while tasks are running.
+#[derive(Default)]
+pub struct Tasks {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    tasks: TaskManager,
+    show_tasks: bool,
+    confirm_quit: bool,
+    state: TableState,
+}
+
+impl Tasks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn select(&mut self, offset: isize) {
+        let len = self.tasks.list().len();
+        if len == 0 {
+            self.state.select(None);
+            return;
+        }
+        let current = self.state.selected().unwrap_or_default() as isize;
+        let index = (current + offset).rem_euclid(len as isize) as usize;
+        self.state.select(Some(index));
+    }
+
+    fn selected_task(&self) -> Option<TaskInfo> {
+        self.state
+            .selected()
+            .and_then(|index| self.tasks.list().get(index).cloned())
+    }
+}
+
+impl Component for Tasks {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn register_task_handler(&mut self, tasks: TaskManager) -> Result<()> {
+        self.tasks = tasks;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.show_tasks || self.confirm_quit
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("Modal")
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.confirm_quit {
+            return Ok(match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => Some(Action::ForceQuit),
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.confirm_quit = false;
+                    None
+                }
+                _ => None,
+            });
+        }
+        if !self.show_tasks {
+            return Ok(None);
+        }
+        let action = match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.select(1);
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.select(-1);
+                None
+            }
+            KeyCode::Char('c') | KeyCode::Delete => {
+                self.selected_task().map(|task| Action::CancelTask(task.id))
+            }
+            KeyCode::Esc => {
+                self.show_tasks = false;
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ToggleShowTasks => {
+                self.show_tasks = !self.show_tasks;
+                self.select(0);
+            }
+            Action::ConfirmQuit => self.confirm_quit = true,
+            Action::TaskCompleted(_) | Action::TaskCancelled(_) => self.select(0),
+            Action::TaskFailed(id, ref e) => {
+                log::error!("Task {id} failed: {e}");
+                self.select(0);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        if self.show_tasks {
+            let rect = area.inner(&Margin {
+                horizontal: 4,
+                vertical: 2,
+            });
+            f.render_widget(Clear, rect);
+            let rows = self
+                .tasks
+                .list()
+                .into_iter()
+                .map(|task| {
+                    let progress = match task.progress {
+                        Some(v) => format!("{:>3.0}%", v * 100.0),
+                        None => String::from("  -"),
+                    };
+                    let state = if task.is_cancelled() {
+                        String::from("cancelling")
+                    } else {
+                        task.message.clone()
+                    };
+                    Row::new(vec![
+                        task.id.to_string(),
+                        format!("{}s", task.elapsed().as_secs()),
+                        task.name,
+                        progress,
+                        state,
+                    ])
+                })
+                .collect::<Vec<Row>>();
+            let table = Table::new(rows)
+                .header(
+                    Row::new(vec!["ID", "Elapsed", "Task", "Progress", "Status"])
+                        .bottom_margin(1)
+                        .style(Style::default().add_modifier(Modifier::BOLD)),
+                )
+                .block(
+                    Block::default()
+                        .title(Line::from(vec![Span::styled(
+                            "Running Tasks",
+                            Style::default().add_modifier(Modifier::BOLD),
+                        )]))
+                        .title(
+                            block::Title::from("j/k: select · c: cancel · esc: close")
+                                .position(block::Position::Bottom)
+                                .alignment(Alignment::Right),
+                        )
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Yellow)),
+                )
+                .widths(&[
+                    Constraint::Length(4),
+                    Constraint::Length(8),
+                    Constraint::Percentage(40),
+                    Constraint::Length(8),
+                    Constraint::Percentage(40),
+                ])
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                .column_spacing(1);
+            f.render_stateful_widget(table, rect, &mut self.state);
+        }
+        if self.confirm_quit {
+            let count = self.tasks.list().len();
+            let rect = centered_rect(area, 50, 7);
+            f.render_widget(Clear, rect);
+            f.render_widget(
+                Paragraph::new(vec![
+                    Line::from(format!(
+                        "{count} task{} still running.",
+                        if count == 1 { " is" } else { "s are" }
+                    )),
+                    Line::default(),
+                    Line::from("Cancel them and quit? (y/n)".bold()),
+                ])
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .title("Quit")
+                        .title_alignment(Alignment::Center)
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(Color::Red))
+                        .padding(Padding::vertical(1)),
+                ),
+                rect,
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use std::env;
+
+use color_eyre::eyre::Result;
+use crossterm::event::KeyEvent;
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Component, Frame};
+use crate::{
+    action::Action,
+    config::Config,
+    terminal::{Screen, Terminal},
+};
+
+/// Popup with a shell that runs in a pseudo-terminal.
+///
+/// The shell keeps running while the popup is hidden and it is only closed
+/// when the shell exits.
+#[derive(Default)]
+pub struct TerminalPane {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    terminal: Option<Terminal>,
+    show_terminal: bool,
+}
+
+impl TerminalPane {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn open(&mut self) -> Result<()> {
+        if self.terminal.is_some() {
+            return Ok(());
+        }
+        let Some(tx) = self.command_tx.clone() else {
+            return Ok(());
+        };
+        let cwd = env::current_dir()?;
+        self.terminal = Some(Terminal::spawn(&cwd, 24, 80, tx)?);
+        Ok(())
+    }
+}
+
+impl Component for TerminalPane {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn focusable(&self) -> bool {
+        self.show_terminal && self.terminal.is_some()
+    }
+
+    fn key_scope(&self) -> Option<&'static str> {
+        Some("Terminal")
+    }
+
+    fn captures_input(&self) -> bool {
+        self.focusable()
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if let (true, Some(terminal)) = (self.show_terminal, &mut self.terminal) {
+            terminal.send_key(key)?;
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ToggleTerminal => {
+                self.show_terminal = !self.show_terminal;
+                if self.show_terminal {
+                    if let Err(e) = self.open() {
+                        log::error!("Failed to open the terminal: {e}");
+                        self.show_terminal = false;
+                        return Ok(Some(Action::Error(e.to_string())));
+                    }
+                }
+            }
+            Action::TerminalExited => {
+                self.terminal = None;
+                self.show_terminal = false;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let (true, Some(terminal)) = (self.show_terminal, &mut self.terminal) else {
+            return Ok(());
+        };
+        let rect = area.inner(&Margin {
+            horizontal: 4,
+            vertical: 2,
+        });
+        f.render_widget(Clear, rect);
+        let block = Block::default()
+            .title(Line::from("Terminal".bold()))
+            .title(
+                block::Title::from("ctrl-t: hide · exit: close")
+                    .position(block::Position::Bottom)
+                    .alignment(Alignment::Right),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(rect);
+        f.render_widget(block, rect);
+        terminal.resize(inner.height, inner.width)?;
+        let parser = terminal.parser();
+        let screen = parser.screen();
+        f.render_widget(Screen(screen), inner);
+        if !screen.hide_cursor() {
+            let (row, col) = screen.cursor_position();
+            f.set_cursor(inner.x + col, inner.y + row);
+        }
+        Ok(())
+    }
+}
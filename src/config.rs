@@ -1,4 +1,8 @@
-use std::{collections::HashMap, fmt, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::PathBuf,
+};
 
 use color_eyre::eyre::Result;
 use config::Value;
@@ -16,6 +20,15 @@ use crate::tab::Tab;
 
 const CONFIG: &str = include_str!("../.config/config.json5");
 
+/// Names of the configuration files that are looked up in the config directory.
+const CONFIG_FILES: [(&str, config::FileFormat); 5] = [
+    ("config.json5", config::FileFormat::Json5),
+    ("config.json", config::FileFormat::Json),
+    ("config.yaml", config::FileFormat::Yaml),
+    ("config.toml", config::FileFormat::Toml),
+    ("config.ini", config::FileFormat::Ini),
+];
+
 #[derive(Clone, Debug, Deserialize, Default)]
 pub struct AppConfig {
     #[serde(default)]
@@ -24,6 +37,169 @@ pub struct AppConfig {
     pub _config_dir: PathBuf,
 }
 
+/// Account or environment to connect to.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Profile {
+    /// URL of the API, defaults to the Shuttle API.
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Reference to the API key.
+    ///
+    /// It can be `env:<VARIABLE>`, `file:<PATH>`, `cargo-shuttle` for the key
+    /// stored by `cargo shuttle login`, or the key itself.
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+/// Settings of the local run in the Local tab.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LocalConfig {
+    /// Command line that is run in the shell.
+    pub command: String,
+    /// Paths that trigger a restart when they change.
+    pub watch: Vec<PathBuf>,
+    /// Whether to restart when a watched path changes.
+    pub restart_on_change: bool,
+}
+
+impl Default for LocalConfig {
+    fn default() -> Self {
+        Self {
+            command: String::from("cargo shuttle run"),
+            watch: vec![
+                PathBuf::from("src"),
+                PathBuf::from("Cargo.toml"),
+                PathBuf::from("Shuttle.toml"),
+            ],
+            restart_on_change: true,
+        }
+    }
+}
+
+/// Settings of the service probes of the Dashboard tab.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DashboardConfig {
+    /// Seconds between the probes of the running services, or 0 to disable them.
+    pub probe_interval: u64,
+    /// Number of probes that are kept per service.
+    pub history: usize,
+}
+
+impl Default for DashboardConfig {
+    fn default() -> Self {
+        Self {
+            probe_interval: 30,
+            history: 60,
+        }
+    }
+}
+
+/// Settings of the polling of the platform.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PlatformConfig {
+    /// Seconds between the refreshes of the platform state, or 0 to only
+    /// refresh it on demand.
+    pub refresh_interval: u64,
+}
+
+impl Default for PlatformConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval: 30,
+        }
+    }
+}
+
+/// Escape sequence of the desktop notifications.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DesktopProtocol {
+    /// `OSC 9`, as understood by iTerm2, kitty, WezTerm and Windows Terminal.
+    #[default]
+    Osc9,
+    /// `OSC 777`, as understood by urxvt, foot and VTE-based terminals.
+    Osc777,
+}
+
+/// How a deployment event is signalled while the terminal is in the background.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct EventAlerts {
+    /// Rings the terminal bell.
+    pub bell: bool,
+    /// Sends a desktop notification.
+    pub desktop: bool,
+    /// Shows the event in the title of the terminal window.
+    pub title: bool,
+}
+
+impl Default for EventAlerts {
+    fn default() -> Self {
+        Self {
+            bell: true,
+            desktop: true,
+            title: true,
+        }
+    }
+}
+
+/// Settings of the notifications of deployment events while the terminal is
+/// in the background.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    pub protocol: DesktopProtocol,
+    /// A deployment started running.
+    pub deployed: EventAlerts,
+    /// A deployment crashed.
+    pub crashed: EventAlerts,
+    /// A deployment stopped or completed.
+    pub stopped: EventAlerts,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            protocol: DesktopProtocol::default(),
+            deployed: EventAlerts::default(),
+            crashed: EventAlerts::default(),
+            stopped: EventAlerts {
+                bell: false,
+                desktop: false,
+                title: true,
+            },
+        }
+    }
+}
+
+/// Where the output of a command is shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    #[default]
+    Popup,
+    Notification,
+}
+
+/// Command of the `commands` section, bound with `Command(<name>)`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct UserCommand {
+    /// Actions that are run in order.
+    #[serde(default)]
+    pub actions: Vec<Action>,
+    /// Command line that is run in the shell after the actions.
+    ///
+    /// `{project}`, `{deployment_id}` and `{service_url}` are replaced with
+    /// the selected project and its current deployment, quoted for the shell.
+    #[serde(default)]
+    pub run: Option<String>,
+    #[serde(default)]
+    pub output: OutputMode,
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 pub struct Config {
     #[serde(default, flatten)]
@@ -32,6 +208,20 @@ pub struct Config {
     pub keybindings: KeyBindings,
     #[serde(default)]
     pub styles: Styles,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    #[serde(default)]
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub local: LocalConfig,
+    #[serde(default)]
+    pub dashboard: DashboardConfig,
+    #[serde(default)]
+    pub platform: PlatformConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub commands: BTreeMap<String, UserCommand>,
 }
 
 impl Config {
@@ -43,15 +233,8 @@ impl Config {
             .set_default("_data_dir", data_dir.to_str().unwrap())?
             .set_default("_config_dir", config_dir.to_str().unwrap())?;
 
-        let config_files = [
-            ("config.json5", config::FileFormat::Json5),
-            ("config.json", config::FileFormat::Json),
-            ("config.yaml", config::FileFormat::Yaml),
-            ("config.toml", config::FileFormat::Toml),
-            ("config.ini", config::FileFormat::Ini),
-        ];
         let mut found_config = false;
-        for (file, format) in &config_files {
+        for (file, format) in &CONFIG_FILES {
             builder = builder.add_source(
                 config::File::from(config_dir.join(file))
                     .format(*format)
@@ -67,8 +250,8 @@ impl Config {
 
         let mut cfg: Self = builder.build()?.try_deserialize()?;
 
-        for (mode, default_bindings) in default_config.keybindings.iter() {
-            let user_bindings = cfg.keybindings.entry(*mode).or_default();
+        for (scope, default_bindings) in default_config.keybindings.iter() {
+            let user_bindings = cfg.keybindings.entry(scope.clone()).or_default();
             for (key, cmd) in default_bindings.iter() {
                 user_bindings
                     .entry(key.clone())
@@ -86,28 +269,99 @@ impl Config {
 
         Ok(cfg)
     }
+
+    /// Returns the configuration files that exist in the config directory.
+    pub fn files() -> Vec<PathBuf> {
+        let config_dir = crate::utils::get_config_dir();
+        CONFIG_FILES
+            .iter()
+            .map(|(file, _)| config_dir.join(file))
+            .filter(|path| path.exists())
+            .collect()
+    }
+}
+
+/// Part of the application that a set of key bindings applies to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// Bindings that are inherited by every tab.
+    Global,
+    Tab(Tab),
+    /// Bindings of a component, see [`Component::key_scope`].
+    ///
+    /// [`Component::key_scope`]: crate::components::Component::key_scope
+    Component(String),
+}
+
+impl From<&str> for Scope {
+    fn from(name: &str) -> Self {
+        match name {
+            "Global" => Scope::Global,
+            name => name
+                .parse()
+                .map(Scope::Tab)
+                .unwrap_or_else(|_| Scope::Component(name.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::Global => write!(f, "Global"),
+            Scope::Tab(tab) => write!(f, "{tab}"),
+            Scope::Component(name) => write!(f, "{name}"),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Deref, DerefMut)]
-pub struct KeyBindings(pub HashMap<Tab, HashMap<Vec<KeyEvent>, Action>>);
+pub struct KeyBindings(pub HashMap<Scope, HashMap<Vec<KeyEvent>, Action>>);
+
+impl KeyBindings {
+    /// Returns the action that is bound to the keys in the innermost scope
+    /// that binds them.
+    ///
+    /// The scopes are ordered from the innermost to the outermost. A binding
+    /// to `Action::Nop` hides the bindings of the outer scopes.
+    pub fn resolve(&self, scopes: &[Scope], keys: &[KeyEvent]) -> Option<&Action> {
+        scopes
+            .iter()
+            .find_map(|scope| self.get(scope)?.get(keys))
+            .filter(|action| **action != Action::Nop)
+    }
+
+    /// Returns `true` if the keys are the beginning of a longer binding.
+    pub fn is_prefix(&self, scopes: &[Scope], keys: &[KeyEvent]) -> bool {
+        scopes
+            .iter()
+            .filter_map(|scope| self.get(scope))
+            .flat_map(|bindings| bindings.keys())
+            .any(|v| v.len() > keys.len() && v.starts_with(keys))
+    }
+}
 
 impl<'de> Deserialize<'de> for KeyBindings {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let parsed_map = HashMap::<Tab, HashMap<String, Action>>::deserialize(deserializer)?;
+        let parsed_map = HashMap::<String, HashMap<String, Action>>::deserialize(deserializer)?;
 
         let keybindings = parsed_map
             .into_iter()
             .map(|(mode, inner_map)| {
                 let converted_inner_map = inner_map
                     .into_iter()
-                    .map(|(key_str, cmd)| (parse_key_sequence(&key_str).unwrap(), cmd))
-                    .collect();
-                (mode, converted_inner_map)
+                    .map(|(key_str, cmd)| {
+                        parse_key_sequence(&key_str)
+                            .map(|keys| (keys, cmd))
+                            .map_err(de::Error::custom)
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok((Scope::from(mode.as_str()), converted_inner_map))
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         Ok(KeyBindings(keybindings))
     }
@@ -418,6 +672,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::layout::LayoutCommand;
 
     #[test]
     fn test_parse_style_default() {
@@ -471,7 +726,7 @@ mod tests {
         let c = Config::new()?;
         assert_eq!(
             c.keybindings
-                .get(&Tab::Home)
+                .get(&Scope::Global)
                 .unwrap()
                 .get(&parse_key_sequence("<q>").unwrap_or_default())
                 .unwrap(),
@@ -480,6 +735,85 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_layout_bindings() -> Result<()> {
+        let c = Config::new()?;
+        assert_eq!(
+            c.keybindings
+                .get(&Scope::Global)
+                .unwrap()
+                .get(&parse_key_sequence("<Ctrl-w><->").unwrap_or_default())
+                .unwrap(),
+            &Action::Layout(LayoutCommand::Shrink)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_commands() {
+        let config: Config = json5::from_str(
+            r#"{
+              "commands": {
+                "status": { "run": "cargo shuttle status --name {project}" },
+                "ping": { "actions": ["Refresh", "GoToTab(Deployments)"], "output": "notification" },
+              }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.commands["status"],
+            UserCommand {
+                actions: Vec::new(),
+                run: Some(String::from("cargo shuttle status --name {project}")),
+                output: OutputMode::Popup,
+            }
+        );
+        assert_eq!(
+            config.commands["ping"],
+            UserCommand {
+                actions: vec![Action::Refresh, Action::GoToTab(Tab::Deployments)],
+                run: None,
+                output: OutputMode::Notification,
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_scopes() {
+        let bindings: KeyBindings = json5::from_str(
+            r#"{
+              "Global": { "<q>": "Quit", "<h>": "PreviousTab", "<Ctrl-w><z>": "ZoomPane" },
+              "Deployments": { "<q>": "Refresh" },
+              "Modal": { "<q>": "<Nop>", "<h>": "Nop" },
+            }"#,
+        )
+        .unwrap();
+        let key = |raw| parse_key_sequence(raw).unwrap();
+        let scopes = [Scope::Tab(Tab::Deployments), Scope::Global];
+        assert_eq!(
+            bindings.resolve(&scopes, &key("<q>")),
+            Some(&Action::Refresh)
+        );
+        assert_eq!(
+            bindings.resolve(&scopes, &key("<h>")),
+            Some(&Action::PreviousTab)
+        );
+        assert!(bindings.is_prefix(&scopes, &key("<Ctrl-w>")));
+        assert!(!bindings.is_prefix(&scopes, &key("<Ctrl-w><z>")));
+
+        let scopes = [
+            Scope::from("Modal"),
+            Scope::Tab(Tab::Deployments),
+            Scope::Global,
+        ];
+        assert_eq!(bindings.resolve(&scopes, &key("<q>")), None);
+        assert_eq!(bindings.resolve(&scopes, &key("<h>")), None);
+        assert_eq!(
+            Scope::from("Modal"),
+            Scope::Component(String::from("Modal"))
+        );
+    }
+
     #[test]
     fn test_simple_keys() {
         assert_eq!(
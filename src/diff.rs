@@ -0,0 +1,211 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+
+use crate::shuttle::models::{Deployment, Resource};
+
+/// Name of the Shuttle manifest.
+pub const MANIFEST_FILE: &str = "Shuttle.toml";
+
+/// Type of the resource that holds the secrets of a service.
+const SECRETS_RESOURCE: &str = "secrets";
+
+/// Change of an entry between two deployments.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Change {
+    Added(String),
+    Removed(String),
+    Unchanged(String),
+}
+
+/// One side of a line in a side-by-side diff.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffLine {
+    Unchanged(String),
+    Removed(String),
+    Added(String),
+    /// Padding for a line that only exists on the other side.
+    Empty,
+}
+
+/// Comparison of two deployments of the same project.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploymentDiff {
+    pub project: String,
+    pub from: Deployment,
+    pub to: Deployment,
+    /// Commits between the two deployments, if they are available in the local repository.
+    pub commits: Option<Vec<String>>,
+    /// Changes of the resource types, if the resources of both deployments are known.
+    pub resources: Option<Vec<Change>>,
+    /// Changes of the secret names, if the resources of both deployments are known.
+    pub secrets: Option<Vec<Change>>,
+    /// Side-by-side diff of `Shuttle.toml`, if it exists at either commit.
+    pub manifest: Option<Vec<(DiffLine, DiffLine)>>,
+}
+
+impl DeploymentDiff {
+    /// Compares the deployments with the given resources and manifests.
+    ///
+    /// The deployments are ordered so that `from` is the older one.
+    pub fn new(
+        project: String,
+        deployments: [Deployment; 2],
+        resources: [Option<&[Resource]>; 2],
+        manifests: [Option<String>; 2],
+        commits: Option<Vec<String>>,
+    ) -> Self {
+        let [from, to] = deployments;
+        let (resources, secrets) = match resources {
+            [Some(old), Some(new)] => (
+                Some(compare_sets(&resource_types(old), &resource_types(new))),
+                Some(compare_sets(&secret_names(old), &secret_names(new))),
+            ),
+            _ => (None, None),
+        };
+        let manifest = match manifests {
+            [None, None] => None,
+            [old, new] => Some(side_by_side(
+                &old.unwrap_or_default(),
+                &new.unwrap_or_default(),
+            )),
+        };
+        Self {
+            project,
+            from,
+            to,
+            commits,
+            resources,
+            secrets,
+            manifest,
+        }
+    }
+}
+
+/// Returns the types of the given resources.
+pub fn resource_types(resources: &[Resource]) -> BTreeSet<String> {
+    resources
+        .iter()
+        .map(|resource| resource.r#type.clone())
+        .collect()
+}
+
+/// Returns the names of the secrets among the given resources, without their values.
+pub fn secret_names(resources: &[Resource]) -> BTreeSet<String> {
+    resources
+        .iter()
+        .filter(|resource| resource.r#type == SECRETS_RESOURCE)
+        .filter_map(|resource| resource.data.as_object())
+        .flat_map(|secrets| secrets.keys().cloned())
+        .collect()
+}
+
+/// Compares two sets, listing the entries in order.
+pub fn compare_sets(old: &BTreeSet<String>, new: &BTreeSet<String>) -> Vec<Change> {
+    old.union(new)
+        .map(|entry| match (old.contains(entry), new.contains(entry)) {
+            (true, false) => Change::Removed(entry.clone()),
+            (false, true) => Change::Added(entry.clone()),
+            _ => Change::Unchanged(entry.clone()),
+        })
+        .collect()
+}
+
+/// Returns a line-based diff of two texts as aligned pairs of lines.
+///
+/// Removed lines are paired with added lines where possible, the remaining
+/// ones are padded with [`DiffLine::Empty`].
+pub fn side_by_side(old: &str, new: &str) -> Vec<(DiffLine, DiffLine)> {
+    let mut lines = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    let flush = |lines: &mut Vec<(DiffLine, DiffLine)>,
+                 removed: &mut Vec<String>,
+                 added: &mut Vec<String>| {
+        let len = removed.len().max(added.len());
+        let mut removed = removed.drain(..);
+        let mut added = added.drain(..);
+        for _ in 0..len {
+            lines.push((
+                removed
+                    .next()
+                    .map(DiffLine::Removed)
+                    .unwrap_or(DiffLine::Empty),
+                added.next().map(DiffLine::Added).unwrap_or(DiffLine::Empty),
+            ));
+        }
+    };
+    for change in TextDiff::from_lines(old, new).iter_all_changes() {
+        let line = change.value().trim_end_matches('\n').to_string();
+        match change.tag() {
+            ChangeTag::Delete => removed.push(line),
+            ChangeTag::Insert => added.push(line),
+            ChangeTag::Equal => {
+                flush(&mut lines, &mut removed, &mut added);
+                lines.push((DiffLine::Unchanged(line.clone()), DiffLine::Unchanged(line)));
+            }
+        }
+    }
+    flush(&mut lines, &mut removed, &mut added);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_secret_names() {
+        let resources = [
+            Resource {
+                r#type: String::from("secrets"),
+                config: json!({}),
+                data: json!({ "API_KEY": "hunter2", "DATABASE_URL": "postgres://" }),
+            },
+            Resource {
+                r#type: String::from("persist"),
+                config: json!({}),
+                data: json!({ "path": "/data" }),
+            },
+        ];
+        assert_eq!(
+            secret_names(&resources),
+            BTreeSet::from([String::from("API_KEY"), String::from("DATABASE_URL")])
+        );
+        assert_eq!(
+            compare_sets(
+                &BTreeSet::from([String::from("API_KEY"), String::from("TOKEN")]),
+                &secret_names(&resources)
+            ),
+            vec![
+                Change::Unchanged(String::from("API_KEY")),
+                Change::Added(String::from("DATABASE_URL")),
+                Change::Removed(String::from("TOKEN")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let old = "name = \"app\"\nassets = []\n";
+        let new = "name = \"app\"\nassets = [\"static\"]\nidle = 30\n";
+        assert_eq!(
+            side_by_side(old, new),
+            vec![
+                (
+                    DiffLine::Unchanged(String::from("name = \"app\"")),
+                    DiffLine::Unchanged(String::from("name = \"app\""))
+                ),
+                (
+                    DiffLine::Removed(String::from("assets = []")),
+                    DiffLine::Added(String::from("assets = [\"static\"]"))
+                ),
+                (DiffLine::Empty, DiffLine::Added(String::from("idle = 30"))),
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    cache::PlatformState,
+    shuttle::models::{Deployment, Resource},
+};
+
+/// Node of the resource graph of a project.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+    Service {
+        name: String,
+        uri: Option<String>,
+    },
+    Deployment {
+        deployment: Deployment,
+        /// Whether the service currently runs the deployment.
+        current: bool,
+        /// Whether it is the last deployment of the service.
+        last: bool,
+    },
+    Resource {
+        resource: Resource,
+        /// Whether the resource is still bound by the running deployment.
+        provisioned: bool,
+        /// Whether it is the last resource of the deployment.
+        last: bool,
+        /// Whether the deployment that binds it is the last one.
+        last_deployment: bool,
+    },
+}
+
+/// Returns the nodes of the graph of a project, in the order they are drawn.
+///
+/// The service comes first, followed by the deployments whose resources are
+/// known, each with the resources that it binds.
+pub fn build(state: &PlatformState, project: &str) -> Vec<Node> {
+    let service = state.services.get(project);
+    let current = service
+        .and_then(|service| service.deployment.as_ref())
+        .map(|deployment| deployment.id.as_str());
+    let provisioned = state.resources(project);
+    let deployments = state
+        .deployments(project)
+        .iter()
+        .filter_map(|deployment| {
+            let is_current = current == Some(deployment.id.as_str());
+            let resources = match state.deployment_resources.get(&deployment.id) {
+                Some(resources) => resources.as_slice(),
+                None if is_current => provisioned,
+                None => return None,
+            };
+            Some((deployment, is_current, resources))
+        })
+        .collect::<Vec<_>>();
+
+    let mut nodes = vec![Node::Service {
+        name: project.to_string(),
+        uri: service.map(|service| service.uri.clone()),
+    }];
+    for (i, (deployment, current, resources)) in deployments.iter().enumerate() {
+        let last_deployment = i + 1 == deployments.len();
+        nodes.push(Node::Deployment {
+            deployment: (*deployment).clone(),
+            current: *current,
+            last: last_deployment,
+        });
+        for (j, resource) in resources.iter().enumerate() {
+            nodes.push(Node::Resource {
+                resource: resource.clone(),
+                provisioned: provisioned.iter().any(|v| v.r#type == resource.r#type),
+                last: j + 1 == resources.len(),
+                last_deployment,
+            });
+        }
+    }
+    nodes
+}
+
+/// Returns the kind of a resource type, e.g. `database` for
+/// `database::shared::postgres`.
+pub fn resource_kind(r#type: &str) -> &'static str {
+    match r#type.split("::").next().unwrap_or_default() {
+        "database" => "database",
+        "secrets" => "secrets",
+        "persist" => "persist",
+        "static_folder" | "static" => "static folder",
+        _ => "resource",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::shuttle::models::{DeploymentState, Service};
+
+    fn deployment(id: &str) -> Deployment {
+        Deployment {
+            id: id.to_string(),
+            service_id: String::new(),
+            state: DeploymentState::Running,
+            last_update: Utc::now(),
+            git_commit_id: None,
+            git_commit_msg: None,
+            git_branch: None,
+            git_dirty: None,
+        }
+    }
+
+    fn resource(r#type: &str) -> Resource {
+        Resource {
+            r#type: r#type.to_string(),
+            config: Default::default(),
+            data: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        let state = PlatformState {
+            services: BTreeMap::from([(
+                String::from("api"),
+                Service {
+                    name: String::from("api"),
+                    deployment: Some(deployment("2")),
+                    uri: String::from("https://api.shuttleapp.rs"),
+                },
+            )]),
+            deployments: BTreeMap::from([(
+                String::from("api"),
+                vec![deployment("2"), deployment("1"), deployment("0")],
+            )]),
+            resources: BTreeMap::from([(String::from("api"), vec![resource("secrets")])]),
+            deployment_resources: BTreeMap::from([(
+                String::from("1"),
+                vec![resource("database::shared::postgres"), resource("secrets")],
+            )]),
+            ..Default::default()
+        };
+        let nodes = build(&state, "api");
+        let summary = nodes
+            .iter()
+            .map(|node| match node {
+                Node::Service { name, .. } => format!("service {name}"),
+                Node::Deployment {
+                    deployment,
+                    current,
+                    last,
+                } => format!("deployment {} {current} {last}", deployment.id),
+                Node::Resource {
+                    resource,
+                    provisioned,
+                    last,
+                    ..
+                } => format!("resource {} {provisioned} {last}", resource.r#type),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                "service api",
+                "deployment 2 true false",
+                "resource secrets true true",
+                "deployment 1 false true",
+                "resource database::shared::postgres false false",
+                "resource secrets true true",
+            ]
+        );
+        assert_eq!(resource_kind("database::shared::postgres"), "database");
+    }
+}
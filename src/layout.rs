@@ -0,0 +1,511 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use color_eyre::eyre::Result;
+use enum_iterator::all;
+use ratatui::layout::Rect;
+use serde::{Deserialize, Serialize};
+
+use crate::{tab::Tab, utils::get_data_dir};
+
+const LAYOUTS_FILE: &str = "layouts.json";
+
+/// Smallest share of a split that a pane can be resized to, in percent.
+const MIN_RATIO: u16 = 10;
+
+/// Change of the share of a split when a pane is resized, in percent.
+const RESIZE_STEP: i16 = 5;
+
+/// Size of the canvas that is used to find neighbouring panes.
+const CANVAS: Rect = Rect {
+    x: 0,
+    y: 0,
+    width: 200,
+    height: 200,
+};
+
+/// How the two halves of a split are arranged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Orientation {
+    /// Side by side.
+    Horizontal,
+    /// Stacked on top of each other.
+    Vertical,
+}
+
+/// Direction in which the focus is moved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    Left,
+    Down,
+    Up,
+    Right,
+}
+
+/// Change to the layout of the current tab.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum LayoutCommand {
+    Focus(Side),
+    Split(Orientation),
+    Close,
+    /// Shows another view in the focused pane.
+    Cycle,
+    Grow,
+    Shrink,
+    ToggleZoom,
+    Equalize,
+}
+
+/// Node of a layout tree.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Node {
+    /// Pane that shows the components of a view.
+    Pane(Tab),
+    Split {
+        orientation: Orientation,
+        /// Share of the first half, in percent.
+        ratio: u16,
+        first: Box<Node>,
+        second: Box<Node>,
+    },
+}
+
+impl Node {
+    fn split(orientation: Orientation, first: Node, second: Node) -> Self {
+        Node::Split {
+            orientation,
+            ratio: 50,
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    /// Returns the views of the panes in order.
+    fn views(&self) -> Vec<Tab> {
+        match self {
+            Node::Pane(view) => vec![*view],
+            Node::Split { first, second, .. } => {
+                let mut views = first.views();
+                views.extend(second.views());
+                views
+            }
+        }
+    }
+
+    fn areas(&self, area: Rect, areas: &mut Vec<(Tab, Rect)>) {
+        match self {
+            Node::Pane(view) => areas.push((*view, area)),
+            Node::Split {
+                orientation,
+                ratio,
+                first,
+                second,
+            } => {
+                let (a, b) = split_rect(area, *orientation, *ratio);
+                first.areas(a, areas);
+                second.areas(b, areas);
+            }
+        }
+    }
+
+    /// Returns the pane with the given index along with the index of the first
+    /// pane after this node.
+    fn pane_mut(&mut self, index: usize, offset: usize) -> (Option<&mut Node>, usize) {
+        match self {
+            Node::Pane(_) if index == offset => (Some(self), offset + 1),
+            Node::Pane(_) => (None, offset + 1),
+            Node::Split { first, second, .. } => match first.pane_mut(index, offset) {
+                (Some(node), end) => (Some(node), end),
+                (None, end) => second.pane_mut(index, end),
+            },
+        }
+    }
+
+    /// Replaces the split that holds the pane with the given index by the other
+    /// half of the split.
+    fn remove(&mut self, index: usize) -> bool {
+        let Node::Split { first, second, .. } = self else {
+            return false;
+        };
+        let count = first.views().len();
+        let (target, sibling, index) = if index < count {
+            (first, second, index)
+        } else {
+            (second, first, index - count)
+        };
+        if let Node::Pane(_) = **target {
+            let sibling = std::mem::replace(&mut **sibling, Node::Pane(Tab::Home));
+            *self = sibling;
+            return true;
+        }
+        target.remove(index)
+    }
+
+    /// Adjusts the innermost split that holds the pane with the given index,
+    /// growing the pane by `delta` percent.
+    fn resize(&mut self, index: usize, delta: i16) -> bool {
+        let Node::Split {
+            ratio,
+            first,
+            second,
+            ..
+        } = self
+        else {
+            return false;
+        };
+        let count = first.views().len();
+        let (resized, delta) = if index < count {
+            (first.resize(index, delta), delta)
+        } else {
+            (second.resize(index - count, delta), -delta)
+        };
+        if resized {
+            return true;
+        }
+        *ratio = ratio
+            .saturating_add_signed(delta)
+            .clamp(MIN_RATIO, 100 - MIN_RATIO);
+        true
+    }
+
+    fn equalize(&mut self) {
+        if let Node::Split {
+            ratio,
+            first,
+            second,
+            ..
+        } = self
+        {
+            *ratio = 50;
+            first.equalize();
+            second.equalize();
+        }
+    }
+}
+
+fn split_rect(area: Rect, orientation: Orientation, ratio: u16) -> (Rect, Rect) {
+    match orientation {
+        Orientation::Horizontal => {
+            let width = (area.width as u32 * ratio as u32 / 100) as u16;
+            (
+                Rect { width, ..area },
+                Rect {
+                    x: area.x + width,
+                    width: area.width - width,
+                    ..area
+                },
+            )
+        }
+        Orientation::Vertical => {
+            let height = (area.height as u32 * ratio as u32 / 100) as u16;
+            (
+                Rect { height, ..area },
+                Rect {
+                    y: area.y + height,
+                    height: area.height - height,
+                    ..area
+                },
+            )
+        }
+    }
+}
+
+/// Arrangement of the panes of a tab.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaneLayout {
+    pub root: Node,
+    /// Index of the focused pane.
+    pub focus: usize,
+    /// Whether the focused pane takes the whole area.
+    pub zoomed: bool,
+}
+
+impl PaneLayout {
+    /// Returns a layout with a single pane.
+    pub fn new(view: Tab) -> Self {
+        Self {
+            root: Node::Pane(view),
+            focus: 0,
+            zoomed: false,
+        }
+    }
+
+    /// Returns the views of the panes in order.
+    pub fn views(&self) -> Vec<Tab> {
+        self.root.views()
+    }
+
+    /// Returns the view of the focused pane.
+    pub fn focused(&self) -> Tab {
+        let views = self.views();
+        views[self.focus.min(views.len() - 1)]
+    }
+
+    /// Returns `true` if the view is shown.
+    pub fn is_visible(&self, view: Tab) -> bool {
+        self.visible().contains(&view)
+    }
+
+    /// Returns the views that are drawn, which is only the focused one while
+    /// zoomed.
+    pub fn visible(&self) -> Vec<Tab> {
+        if self.zoomed {
+            vec![self.focused()]
+        } else {
+            self.views()
+        }
+    }
+
+    /// Divides the area between the panes.
+    pub fn areas(&self, area: Rect) -> Vec<(Tab, Rect)> {
+        if self.zoomed {
+            return vec![(self.focused(), area)];
+        }
+        let mut areas = Vec::new();
+        self.root.areas(area, &mut areas);
+        areas
+    }
+
+    /// Moves the focus to the closest pane on the given side.
+    pub fn focus(&mut self, side: Side) -> bool {
+        let mut areas = Vec::new();
+        self.root.areas(CANVAS, &mut areas);
+        let current = areas[self.focus.min(areas.len() - 1)].1;
+        let overlaps = |a: (u16, u16), b: (u16, u16)| a.0 < b.1 && b.0 < a.1;
+        let closest = areas
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (_, rect))| {
+                let (distance, aligned) = match side {
+                    Side::Left => (
+                        current.left().checked_sub(rect.right())?,
+                        overlaps((rect.y, rect.bottom()), (current.y, current.bottom())),
+                    ),
+                    Side::Right => (
+                        rect.left().checked_sub(current.right())?,
+                        overlaps((rect.y, rect.bottom()), (current.y, current.bottom())),
+                    ),
+                    Side::Up => (
+                        current.top().checked_sub(rect.bottom())?,
+                        overlaps((rect.x, rect.right()), (current.x, current.right())),
+                    ),
+                    Side::Down => (
+                        rect.top().checked_sub(current.bottom())?,
+                        overlaps((rect.x, rect.right()), (current.x, current.right())),
+                    ),
+                };
+                aligned.then_some((distance, index))
+            })
+            .min();
+        let Some((_, index)) = closest else {
+            return false;
+        };
+        self.focus = index;
+        self.zoomed = false;
+        true
+    }
+
+    /// Splits the focused pane and focuses the new pane.
+    ///
+    /// The new pane shows the first view that is not shown yet.
+    pub fn split(&mut self, orientation: Orientation) -> bool {
+        let views = self.views();
+        let Some(view) = all::<Tab>().find(|v| !views.contains(v)) else {
+            return false;
+        };
+        let focus = self.focus;
+        let Some(pane) = self.root.pane_mut(focus, 0).0 else {
+            return false;
+        };
+        let current = std::mem::replace(pane, Node::Pane(view));
+        *pane = Node::split(orientation, current, Node::Pane(view));
+        self.focus += 1;
+        self.zoomed = false;
+        true
+    }
+
+    /// Closes the focused pane, unless it is the last one.
+    pub fn close(&mut self) -> bool {
+        if !self.root.remove(self.focus) {
+            return false;
+        }
+        self.focus = self.focus.min(self.views().len() - 1);
+        self.zoomed = false;
+        true
+    }
+
+    /// Shows the next view that is not shown yet in the focused pane.
+    pub fn cycle(&mut self) -> bool {
+        let views = self.views();
+        let focus = self.focus;
+        let Some(Node::Pane(view)) = self.root.pane_mut(focus, 0).0 else {
+            return false;
+        };
+        let current = *view;
+        let next = all::<Tab>()
+            .cycle()
+            .skip_while(|v| *v != current)
+            .skip(1)
+            .take(all::<Tab>().count())
+            .find(|v| !views.contains(v));
+        match next {
+            Some(next) => {
+                *view = next;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Grows the focused pane by `delta` percent of its split.
+    pub fn resize(&mut self, delta: i16) -> bool {
+        self.root.resize(self.focus, delta)
+    }
+
+    /// Gives both halves of every split the same size.
+    pub fn equalize(&mut self) -> bool {
+        self.root.equalize();
+        true
+    }
+
+    /// Applies the command, returning `true` if the layout changed.
+    pub fn apply(&mut self, command: LayoutCommand) -> bool {
+        match command {
+            LayoutCommand::Focus(side) => self.focus(side),
+            LayoutCommand::Split(orientation) => self.split(orientation),
+            LayoutCommand::Close => self.close(),
+            LayoutCommand::Cycle => self.cycle(),
+            LayoutCommand::Grow => self.resize(RESIZE_STEP),
+            LayoutCommand::Shrink => self.resize(-RESIZE_STEP),
+            LayoutCommand::ToggleZoom => self.toggle_zoom(),
+            LayoutCommand::Equalize => self.equalize(),
+        }
+    }
+
+    pub fn toggle_zoom(&mut self) -> bool {
+        self.zoomed = !self.zoomed && self.views().len() > 1;
+        true
+    }
+}
+
+/// Layouts of the tabs that are persisted to the data directory.
+#[derive(Debug, Default)]
+pub struct Layouts {
+    path: PathBuf,
+    layouts: HashMap<Tab, PaneLayout>,
+}
+
+impl Layouts {
+    pub fn load() -> Self {
+        Self::load_from(get_data_dir().join(LAYOUTS_FILE))
+    }
+
+    /// Loads the layouts from the given path.
+    ///
+    /// A missing or invalid file results in a single pane for every tab.
+    pub fn load_from(path: PathBuf) -> Self {
+        let layouts = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                log::warn!("Ignoring invalid layouts at {}: {e}", path.display());
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        Self { path, layouts }
+    }
+
+    /// Returns the layout of the tab.
+    pub fn get(&self, tab: Tab) -> PaneLayout {
+        self.layouts
+            .get(&tab)
+            .cloned()
+            .unwrap_or_else(|| PaneLayout::new(tab))
+    }
+
+    /// Changes the layout of the tab and persists the layouts.
+    pub fn apply(&mut self, tab: Tab, command: LayoutCommand) -> Result<()> {
+        self.update(tab, |layout| layout.apply(command))
+    }
+
+    /// Focuses the pane of the tab with the given index.
+    pub fn select(&mut self, tab: Tab, index: usize) -> Result<()> {
+        self.update(tab, |layout| {
+            let changed = layout.focus != index;
+            layout.focus = index;
+            changed
+        })
+    }
+
+    fn update(&mut self, tab: Tab, f: impl FnOnce(&mut PaneLayout) -> bool) -> Result<()> {
+        let mut layout = self.get(tab);
+        if f(&mut layout) {
+            self.layouts.insert(tab, layout);
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.layouts)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_split_and_focus() {
+        let mut layout = PaneLayout::new(Tab::Projects);
+        assert!(layout.split(Orientation::Horizontal));
+        assert!(layout.split(Orientation::Vertical));
+        assert_eq!(
+            layout.views(),
+            vec![Tab::Projects, Tab::Home, Tab::Deployments]
+        );
+        assert_eq!(layout.focused(), Tab::Deployments);
+        assert_eq!(
+            layout.areas(Rect::new(0, 0, 100, 40)),
+            vec![
+                (Tab::Projects, Rect::new(0, 0, 50, 40)),
+                (Tab::Home, Rect::new(50, 0, 50, 20)),
+                (Tab::Deployments, Rect::new(50, 20, 50, 20)),
+            ]
+        );
+
+        layout.focus(Side::Up);
+        assert_eq!(layout.focused(), Tab::Home);
+        layout.focus(Side::Up);
+        assert_eq!(layout.focused(), Tab::Home);
+        layout.focus(Side::Left);
+        assert_eq!(layout.focused(), Tab::Projects);
+        layout.focus(Side::Right);
+        assert_eq!(layout.focused(), Tab::Home);
+
+        assert!(layout.resize(10));
+        assert_eq!(
+            layout.areas(Rect::new(0, 0, 100, 40))[1],
+            (Tab::Home, Rect::new(50, 0, 50, 24))
+        );
+
+        layout.toggle_zoom();
+        assert_eq!(layout.visible(), vec![Tab::Home]);
+        assert_eq!(
+            layout.areas(Rect::new(0, 0, 100, 40)),
+            vec![(Tab::Home, Rect::new(0, 0, 100, 40))]
+        );
+
+        assert!(layout.close());
+        assert!(!layout.zoomed);
+        assert_eq!(layout.views(), vec![Tab::Projects, Tab::Deployments]);
+        assert_eq!(layout.focused(), Tab::Deployments);
+        assert!(layout.close());
+        assert!(!layout.close());
+        assert_eq!(layout, PaneLayout::new(Tab::Projects));
+    }
+}
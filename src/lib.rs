@@ -5,11 +5,28 @@
 #![allow(unused_variables)]
 
 pub mod action;
+pub mod alert;
+pub mod ansi;
 pub mod app;
 pub mod args;
+pub mod cache;
+pub mod cli;
 pub mod components;
 pub mod config;
+pub mod diff;
+pub mod graph;
+pub mod layout;
+pub mod local;
+pub mod manifest;
+pub mod probe;
+pub mod request;
+pub mod scaffold;
+pub mod scripting;
 pub mod shuttle;
+pub mod sql;
 pub mod tab;
+pub mod task;
+pub mod template;
+pub mod terminal;
 pub mod tui;
 pub mod utils;
@@ -0,0 +1,212 @@
+use std::{path::PathBuf, process::Stdio};
+
+use color_eyre::eyre::{bail, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::{Child, Command},
+    sync::mpsc::UnboundedSender,
+};
+
+use crate::{action::Action, task::TaskContext};
+
+/// Returns a command that runs the given command line in the shell.
+fn shell(command: &str) -> Command {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    };
+    // Keep the colors even though the output is not a terminal.
+    shell
+        .env("CARGO_TERM_COLOR", "always")
+        .env("CLICOLOR_FORCE", "1");
+    #[cfg(unix)]
+    shell.process_group(0);
+    shell
+}
+
+/// Terminates the process group of the child when dropped.
+///
+/// The shell is killed on drop as well, but the processes that it spawned
+/// would keep running without this.
+struct ProcessGroup(Option<u32>);
+
+impl Drop for ProcessGroup {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(pid) = self.0 {
+            // SAFETY: sending a signal has no memory safety requirements.
+            unsafe {
+                libc::kill(-(pid as libc::pid_t), libc::SIGTERM);
+            }
+        }
+    }
+}
+
+/// Runs the command and streams its output until it exits.
+///
+/// The output is sent line by line as `Action::LocalOutput`, followed by
+/// `Action::LocalExited` with the exit code. Cancelling the task terminates
+/// the process.
+pub async fn supervise(command: String, ctx: TaskContext) -> Result<()> {
+    let mut child = match shell(&command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            ctx.send(Action::LocalOutput(format!(
+                "Failed to run `{command}`: {e}"
+            )));
+            ctx.send(Action::LocalExited(None));
+            return Err(e.into());
+        }
+    };
+    let _group = ProcessGroup(child.id());
+    ctx.send(Action::LocalStarted(child.id()));
+    ctx.progress(
+        None,
+        format!("Running (pid {})", child.id().unwrap_or_default()),
+    );
+    let status = stream_output(&mut child, &ctx).await?;
+    ctx.send(Action::LocalExited(status.code()));
+    Ok(())
+}
+
+async fn stream_output(child: &mut Child, ctx: &TaskContext) -> Result<std::process::ExitStatus> {
+    let mut stdout = child.stdout.take().map(|v| BufReader::new(v).lines());
+    let mut stderr = child.stderr.take().map(|v| BufReader::new(v).lines());
+    loop {
+        tokio::select! {
+            Some(Ok(Some(line))) = async { Some(stdout.as_mut()?.next_line().await) } => {
+                ctx.send(Action::LocalOutput(line));
+            }
+            Some(Ok(Some(line))) = async { Some(stderr.as_mut()?.next_line().await) } => {
+                ctx.send(Action::LocalOutput(line));
+            }
+            status = child.wait() => {
+                // Forward what is left in the pipes.
+                if let Some(lines) = stdout.as_mut() {
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        ctx.send(Action::LocalOutput(line));
+                    }
+                }
+                if let Some(lines) = stderr.as_mut() {
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        ctx.send(Action::LocalOutput(line));
+                    }
+                }
+                return Ok(status?);
+            }
+        }
+    }
+}
+
+/// Runs the command line in the shell and returns its output.
+///
+/// A non-zero exit status is an error that carries the last line of the
+/// error output, or else of the output.
+pub async fn run(command: &str) -> Result<String> {
+    let child = shell(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let _group = ProcessGroup(child.id());
+    let output = child.wait_with_output().await?;
+    let stdout = strip_ansi_escapes::strip_str(String::from_utf8_lossy(&output.stdout));
+    if !output.status.success() {
+        let stderr = strip_ansi_escapes::strip_str(String::from_utf8_lossy(&output.stderr));
+        let last_line = |output: &str| {
+            output
+                .lines()
+                .rfind(|line| !line.trim().is_empty())
+                .map(str::to_string)
+        };
+        let reason = last_line(&stderr)
+            .or_else(|| last_line(&stdout))
+            .unwrap_or_else(|| String::from("no output"));
+        bail!("`{command}` failed with {}: {reason}", output.status);
+    }
+    Ok(stdout)
+}
+
+/// Watches the given paths and sends the action on changes.
+///
+/// Paths that do not exist are skipped. The watcher stops when dropped.
+pub fn watch(
+    paths: &[PathBuf],
+    tx: UnboundedSender<Action>,
+    action: Action,
+) -> Result<RecommendedWatcher> {
+    let mut watcher =
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event)
+                if matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                ) =>
+            {
+                let _ = tx.send(action.clone());
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to watch the files: {e}"),
+        })?;
+    for path in paths.iter().filter(|path| path.exists()) {
+        watcher.watch(path, RecursiveMode::Recursive)?;
+    }
+    Ok(watcher)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::task::TaskManager;
+
+    #[tokio::test]
+    async fn test_supervise() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let tasks = TaskManager::new();
+        tasks.register_action_handler(tx);
+        tasks.spawn("Run", |ctx| {
+            supervise(String::from("echo out; echo err >&2; exit 3"), ctx)
+        });
+        let mut output = Vec::new();
+        loop {
+            match rx.recv().await.unwrap() {
+                Action::LocalOutput(line) => output.push(line),
+                Action::LocalExited(code) => {
+                    assert_eq!(code, Some(3));
+                    break;
+                }
+                _ => {}
+            }
+        }
+        output.sort();
+        assert_eq!(output, vec![String::from("err"), String::from("out")]);
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        assert_eq!(run("echo out").await.unwrap(), "out\n");
+        assert_eq!(
+            run("echo out; echo err >&2; exit 3")
+                .await
+                .unwrap_err()
+                .to_string(),
+            "`echo out; echo err >&2; exit 3` failed with exit status: 3: err"
+        );
+    }
+}
@@ -4,6 +4,8 @@ use color_eyre::eyre::Result;
 use shuttle_tui::{
     app::App,
     args::Args,
+    cli,
+    config::Config,
     shuttle::Shuttle,
     utils::{initialize_logging, initialize_panic_handler},
 };
@@ -13,8 +15,11 @@ async fn tokio_main() -> Result<()> {
 
     initialize_panic_handler()?;
 
-    let args = Args::parse();
-    let shuttle = Shuttle;
+    let mut args = Args::parse();
+    let shuttle = Shuttle::from_profile(&Config::new()?, args.profile.as_deref())?;
+    if let Some(command) = args.command.take() {
+        return cli::run(command, &shuttle, args.json).await;
+    }
     let mut app = App::new(shuttle, &args)?;
     app.run().await?;
 
@@ -0,0 +1,391 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use toml::Value;
+
+use crate::diff::MANIFEST_FILE;
+
+/// Name of the manifest of a Cargo package or workspace.
+pub const CARGO_MANIFEST_FILE: &str = "Cargo.toml";
+
+/// Crate that every Shuttle service depends on.
+pub const RUNTIME_CRATE: &str = "shuttle-runtime";
+
+/// Contents of `Shuttle.toml`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShuttleManifest {
+    /// Name of the project, which defaults to the name of the crate.
+    pub name: Option<String>,
+    /// Files that are made available to the service, as glob patterns.
+    pub assets: Vec<String>,
+    /// Files that are deployed even though they are ignored, as glob patterns.
+    pub deploy_include: Vec<String>,
+}
+
+impl ShuttleManifest {
+    pub fn parse(content: &str) -> Result<Self> {
+        let value = content.parse::<Value>()?;
+        let strings = |value: Option<&Value>| {
+            value
+                .and_then(Value::as_array)
+                .map(|v| {
+                    v.iter()
+                        .filter_map(Value::as_str)
+                        .map(String::from)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+        let mut assets = strings(value.get("assets"));
+        assets.extend(strings(
+            value.get("build").and_then(|build| build.get("assets")),
+        ));
+        Ok(Self {
+            name: value.get("name").and_then(Value::as_str).map(String::from),
+            assets,
+            deploy_include: strings(value.get("deploy").and_then(|deploy| deploy.get("include"))),
+        })
+    }
+}
+
+/// Shuttle crate that the service depends on.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShuttleDependency {
+    pub name: String,
+    /// Version requirement, unless the crate comes from a path or a Git repository.
+    pub version: Option<String>,
+}
+
+impl ShuttleDependency {
+    /// Returns `true` if the version requirement does not select the same
+    /// minor release as the given version.
+    ///
+    /// Shuttle releases are `0.x`, so a different minor version is not
+    /// compatible.
+    pub fn is_mismatched(&self, version: &str) -> bool {
+        match (
+            self.version.as_deref().and_then(minor_version),
+            minor_version(version),
+        ) {
+            (Some(required), Some(supported)) => required != supported,
+            _ => false,
+        }
+    }
+}
+
+/// Returns the major and minor version of a version or version requirement.
+fn minor_version(version: &str) -> Option<(u64, u64)> {
+    let version = version
+        .trim()
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .trim_start_matches('v');
+    let mut parts = version.split(['.', '-', '+']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts
+        .next()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_default();
+    Some((major, minor))
+}
+
+/// Returns the name of the package of a Cargo manifest.
+fn package_name(manifest: &Value) -> Option<String> {
+    manifest
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(Value::as_str)
+        .map(String::from)
+}
+
+/// Returns the Shuttle crates of a dependency table, in order.
+///
+/// Dependencies with `workspace = true` take their version from the
+/// `[workspace.dependencies]` table of the workspace root.
+fn shuttle_dependencies(
+    table: Option<&Value>,
+    inherited: Option<&Value>,
+) -> Vec<ShuttleDependency> {
+    let version = |dependency: &Value| match dependency {
+        Value::String(version) => Some(version.clone()),
+        dependency => dependency
+            .get("version")
+            .and_then(Value::as_str)
+            .map(String::from),
+    };
+    table
+        .and_then(Value::as_table)
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| name.starts_with("shuttle-"))
+        .map(|(name, dependency)| {
+            let is_inherited = dependency.get("workspace").and_then(Value::as_bool) == Some(true);
+            ShuttleDependency {
+                name: name.clone(),
+                version: if is_inherited {
+                    inherited.and_then(|v| v.get(name)).and_then(version)
+                } else {
+                    version(dependency)
+                },
+            }
+        })
+        .collect()
+}
+
+/// Returns the directories of the members of a workspace.
+///
+/// Members that end with `/*` are expanded to the subdirectories of their
+/// parent, which is the only glob pattern that is supported.
+fn member_directories(directory: &Path, manifest: &Value) -> Vec<PathBuf> {
+    let Some(workspace) = manifest.get("workspace") else {
+        return Vec::new();
+    };
+    let paths = |key: &str| {
+        workspace
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(|v| directory.join(v.trim_end_matches('/')))
+            .collect::<Vec<_>>()
+    };
+    let excluded = paths("exclude");
+    let mut members = Vec::new();
+    for member in paths("members") {
+        if member.file_name().is_some_and(|v| v == "*") {
+            let Some(Ok(entries)) = member.parent().map(fs::read_dir) else {
+                continue;
+            };
+            let mut entries = entries
+                .filter_map(|entry| entry.ok().map(|v| v.path()))
+                .filter(|v| v.is_dir())
+                .collect::<Vec<_>>();
+            entries.sort();
+            members.extend(entries);
+        } else {
+            members.push(member);
+        }
+    }
+    members.retain(|member| !excluded.contains(member));
+    members
+}
+
+/// Shuttle service in a local directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workspace {
+    /// Name of the crate, or of the member of a virtual workspace that
+    /// depends on the runtime.
+    pub package: Option<String>,
+    pub shuttle: Option<ShuttleManifest>,
+    /// Shuttle crates of the dependencies, including the workspace ones.
+    pub dependencies: Vec<ShuttleDependency>,
+}
+
+impl Workspace {
+    /// Reads the manifests of the directory.
+    ///
+    /// Returns `None` if it does not contain a Cargo package or workspace.
+    pub fn detect(directory: &Path) -> Result<Option<Self>> {
+        let path = directory.join(CARGO_MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let shuttle = directory.join(MANIFEST_FILE);
+        let shuttle = if shuttle.exists() {
+            Some(
+                ShuttleManifest::parse(&fs::read_to_string(&shuttle)?)
+                    .map_err(|e| eyre!("Failed to parse {}: {e}", shuttle.display()))?,
+            )
+        } else {
+            None
+        };
+        let cargo_manifest = fs::read_to_string(&path)?;
+        let value = cargo_manifest
+            .parse::<Value>()
+            .map_err(|e| eyre!("Failed to parse {}: {e}", path.display()))?;
+        let mut members = Vec::new();
+        if value.get("package").is_none() {
+            for member in member_directories(directory, &value) {
+                let path = member.join(CARGO_MANIFEST_FILE);
+                if path.exists() {
+                    members.push(fs::read_to_string(&path)?);
+                }
+            }
+        }
+        let workspace = Self::parse(&cargo_manifest, &members, shuttle)
+            .map_err(|e| eyre!("Failed to parse {}: {e}", path.display()))?;
+        Ok(Some(workspace))
+    }
+
+    /// Parses the manifest of a package or workspace, and those of the members
+    /// of a virtual workspace, whose Shuttle service is the first member that
+    /// depends on the runtime.
+    fn parse(
+        cargo_manifest: &str,
+        members: &[String],
+        shuttle: Option<ShuttleManifest>,
+    ) -> Result<Self> {
+        let value = cargo_manifest.parse::<Value>()?;
+        let inherited = value
+            .get("workspace")
+            .and_then(|workspace| workspace.get("dependencies"));
+        let mut package = package_name(&value);
+        let mut dependencies = shuttle_dependencies(value.get("dependencies"), inherited);
+        if package.is_none() {
+            for member in members {
+                let member = member.parse::<Value>()?;
+                let member_dependencies =
+                    shuttle_dependencies(member.get("dependencies"), inherited);
+                if member_dependencies.iter().any(|v| v.name == RUNTIME_CRATE) {
+                    package = package_name(&member);
+                    dependencies = member_dependencies;
+                    break;
+                }
+            }
+        }
+        dependencies.extend(shuttle_dependencies(inherited, None));
+        dependencies.sort_by(|a, b| a.name.cmp(&b.name));
+        dependencies.dedup_by(|a, b| a.name == b.name);
+        Ok(Self {
+            package,
+            shuttle,
+            dependencies,
+        })
+    }
+
+    /// Returns `true` if the workspace depends on the Shuttle runtime.
+    pub fn is_shuttle_service(&self) -> bool {
+        self.dependencies.iter().any(|v| v.name == RUNTIME_CRATE)
+    }
+
+    /// Returns the name of the project that the service is deployed to.
+    pub fn project_name(&self) -> Option<&str> {
+        self.shuttle
+            .as_ref()
+            .and_then(|shuttle| shuttle.name.as_deref())
+            .or(self.package.as_deref())
+    }
+
+    /// Returns the dependencies that do not match the given runtime version.
+    pub fn mismatches(&self, version: &str) -> Vec<&ShuttleDependency> {
+        self.dependencies
+            .iter()
+            .filter(|dependency| dependency.is_mismatched(version))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_workspace() -> Result<()> {
+        let shuttle = ShuttleManifest::parse(
+            r#"
+                name = "my-api"
+                assets = ["static/*"]
+
+                [deploy]
+                include = ["data/*.db"]
+            "#,
+        )?;
+        let workspace = Workspace::parse(
+            r#"
+                [package]
+                name = "api"
+
+                [dependencies]
+                axum = "0.7.2"
+                shuttle-axum = { version = "0.34", features = ["axum"] }
+                shuttle-runtime = "=0.35.1"
+                shuttle-shared-db = { path = "../shared-db" }
+
+                [workspace.dependencies]
+                shuttle-runtime = "0.35.0"
+            "#,
+            &[],
+            Some(shuttle),
+        )?;
+        assert!(workspace.is_shuttle_service());
+        assert_eq!(workspace.project_name(), Some("my-api"));
+        assert_eq!(
+            workspace.shuttle.as_ref().map(|v| &v.assets[..]),
+            Some(&[String::from("static/*")][..])
+        );
+        assert_eq!(
+            workspace
+                .dependencies
+                .iter()
+                .map(|v| (v.name.as_str(), v.version.as_deref()))
+                .collect::<Vec<_>>(),
+            [
+                ("shuttle-axum", Some("0.34")),
+                ("shuttle-runtime", Some("=0.35.1")),
+                ("shuttle-shared-db", None),
+            ]
+        );
+        assert_eq!(
+            workspace
+                .mismatches("0.35.2")
+                .iter()
+                .map(|v| v.name.as_str())
+                .collect::<Vec<_>>(),
+            ["shuttle-axum"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_virtual_workspace() -> Result<()> {
+        let workspace = Workspace::parse(
+            r#"
+                [workspace]
+                members = ["shared", "api"]
+
+                [workspace.dependencies]
+                shuttle-runtime = "0.35.0"
+                shuttle-axum = "0.35.0"
+            "#,
+            &[
+                String::from(
+                    r#"
+                        [package]
+                        name = "shared"
+                    "#,
+                ),
+                String::from(
+                    r#"
+                        [package]
+                        name = "api"
+
+                        [dependencies]
+                        shuttle-runtime = { workspace = true }
+                        shuttle-axum = { workspace = true, features = ["axum"] }
+                    "#,
+                ),
+            ],
+            None,
+        )?;
+        assert!(workspace.is_shuttle_service());
+        assert_eq!(workspace.project_name(), Some("api"));
+        assert_eq!(
+            workspace
+                .dependencies
+                .iter()
+                .map(|v| (v.name.as_str(), v.version.as_deref()))
+                .collect::<Vec<_>>(),
+            [
+                ("shuttle-axum", Some("0.35.0")),
+                ("shuttle-runtime", Some("0.35.0")),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cache::PlatformState,
+    shuttle::{
+        models::{DeploymentState, HealthCheck, LogItem},
+        Shuttle,
+    },
+};
+
+/// Window of the logs in which the errors are counted.
+pub const ERROR_WINDOW_MINUTES: i64 = 60;
+
+/// Time after which a probe counts as failed, even if its requests hang.
+pub const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Result of probing a running service.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Probe {
+    pub project: String,
+    pub deployment_id: String,
+    pub check: HealthCheck,
+    /// Number of errors in the recent logs, unless they could not be fetched.
+    pub errors: Option<usize>,
+}
+
+/// Returns the projects, deployments and URIs of the running services.
+pub fn targets(state: &PlatformState) -> Vec<(String, String, String)> {
+    state
+        .services
+        .iter()
+        .filter_map(|(project, service)| {
+            let deployment = service.deployment.as_ref()?;
+            (deployment.state == DeploymentState::Running && !service.uri.is_empty())
+                .then(|| (project.clone(), deployment.id.clone(), service.uri.clone()))
+        })
+        .collect()
+}
+
+/// Probes the URI of a service and counts the errors in its recent logs.
+pub async fn probe(shuttle: &Shuttle, project: String, deployment_id: String, uri: &str) -> Probe {
+    let (check, logs) = tokio::join!(
+        shuttle.check_health(uri),
+        shuttle.get_logs(&project, &deployment_id)
+    );
+    let since = Utc::now() - Duration::minutes(ERROR_WINDOW_MINUTES);
+    let errors = match logs {
+        Ok(logs) => Some(count_errors(&logs, since)),
+        Err(e) => {
+            log::debug!("Failed to fetch the logs of {project}: {e}");
+            None
+        }
+    };
+    Probe {
+        project,
+        deployment_id,
+        check,
+        errors,
+    }
+}
+
+/// Returns the failed probe of a service that did not answer in time.
+pub fn timed_out(project: String, deployment_id: String, uri: &str) -> Probe {
+    Probe {
+        project,
+        deployment_id,
+        check: HealthCheck {
+            endpoint: uri.to_string(),
+            checked_at: Utc::now(),
+            status: None,
+            latency_ms: PROBE_TIMEOUT.as_millis() as u64,
+            error: Some(String::from("Timed out")),
+        },
+        errors: None,
+    }
+}
+
+/// Returns `true` if a log line reports an error or a panic.
+fn is_error(line: &str) -> bool {
+    line.contains("ERROR") || line.contains("panicked at")
+}
+
+/// Counts the log lines since the given time that report an error.
+pub fn count_errors(logs: &[LogItem], since: DateTime<Utc>) -> usize {
+    logs.iter()
+        .filter(|log| log.timestamp >= since && is_error(&log.line))
+        .count()
+}
+
+/// Probes of a service, the oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServiceHealth {
+    pub deployment_id: String,
+    pub checks: VecDeque<HealthCheck>,
+    pub errors: Option<usize>,
+}
+
+impl ServiceHealth {
+    /// Records a probe, keeping the given number of checks.
+    ///
+    /// The checks start over when the service runs a new deployment.
+    pub fn record(&mut self, probe: Probe, capacity: usize) {
+        if self.deployment_id != probe.deployment_id {
+            self.checks.clear();
+            self.deployment_id = probe.deployment_id;
+        }
+        if self.checks.len() >= capacity.max(1) {
+            self.checks.pop_front();
+        }
+        self.checks.push_back(probe.check);
+        self.errors = probe.errors;
+    }
+
+    pub fn last(&self) -> Option<&HealthCheck> {
+        self.checks.back()
+    }
+
+    /// Returns the share of the checks that were healthy.
+    pub fn uptime(&self) -> Option<f64> {
+        if self.checks.is_empty() {
+            return None;
+        }
+        let healthy = self.checks.iter().filter(|v| v.is_healthy()).count();
+        Some(healthy as f64 / self.checks.len() as f64)
+    }
+
+    /// Returns the latencies of the checks, with failed checks as 0.
+    pub fn latencies(&self) -> Vec<u64> {
+        self.checks
+            .iter()
+            .map(|v| if v.status.is_some() { v.latency_ms } else { 0 })
+            .collect()
+    }
+
+    /// Returns the average latency of the checks that got a response.
+    pub fn average_latency(&self) -> Option<u64> {
+        let latencies = self
+            .checks
+            .iter()
+            .filter(|v| v.status.is_some())
+            .map(|v| v.latency_ms)
+            .collect::<Vec<_>>();
+        (!latencies.is_empty()).then(|| latencies.iter().sum::<u64>() / latencies.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_probe() {
+        // Stands in for both the API and the service.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0; 4096];
+                let size = stream.read(&mut request).await.unwrap_or_default();
+                let request = String::from_utf8_lossy(&request[..size]);
+                let body = if request.starts_with("GET /projects/") {
+                    let now = Utc::now().to_rfc3339();
+                    format!(
+                        r#"[{{"timestamp":"{now}","line":"ERROR api: boom"}},
+                            {{"timestamp":"{now}","line":"INFO api: ok"}},
+                            {{"timestamp":"2020-01-01T00:00:00Z","line":"ERROR api: old"}}]"#
+                    )
+                } else {
+                    String::new()
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        let shuttle = Shuttle::with_api(format!("http://{address}"), None).unwrap();
+        let probe = probe(
+            &shuttle,
+            String::from("api"),
+            String::from("1"),
+            &format!("http://{address}/"),
+        )
+        .await;
+        assert_eq!(probe.check.status, Some(200));
+        assert_eq!(probe.errors, Some(1));
+
+        let mut health = ServiceHealth::default();
+        let mut failed = probe.clone();
+        failed.check.status = None;
+        for probe in [probe.clone(), failed, probe.clone()] {
+            health.record(probe, 2);
+        }
+        assert_eq!(health.checks.len(), 2);
+        assert_eq!(health.uptime(), Some(0.5));
+        assert_eq!(health.latencies()[0], 0);
+        health.record(
+            Probe {
+                deployment_id: String::from("2"),
+                ..probe
+            },
+            2,
+        );
+        assert_eq!(health.checks.len(), 1);
+    }
+}
@@ -0,0 +1,237 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::get_data_dir;
+
+/// Directory of the data directory with the saved requests of each project.
+pub const REQUESTS_DIR: &str = "requests";
+
+/// Methods that a request can use, in the order they are cycled through.
+pub const METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// HTTP request to an endpoint of a service.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpRequest {
+    pub name: String,
+    pub method: String,
+    /// Path relative to the URI of the service, e.g. `/users?page=2`.
+    pub path: String,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    #[serde(default)]
+    pub body: String,
+}
+
+impl HttpRequest {
+    /// Returns the URL of the request for a service with the given URI.
+    pub fn url(&self, uri: &str) -> String {
+        format!(
+            "{}/{}",
+            uri.trim_end_matches('/'),
+            self.path.trim_start_matches('/')
+        )
+    }
+}
+
+/// Parses headers that are given one per line as `Name: value`.
+pub fn parse_headers(text: &str) -> Result<Vec<(String, String)>, String> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("Expected `Name: value`, found `{line}`"))?;
+            let name = name.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(format!("Invalid header name `{name}`"));
+            }
+            Ok((name.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Formats headers one per line, the way [`parse_headers`] reads them.
+pub fn format_headers(headers: &[(String, String)]) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| format!("{name}: {value}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Response of a service to a request.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// Reason phrase of the status, e.g. `Not Found`.
+    pub reason: String,
+    pub elapsed: Duration,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn is_success(&self) -> bool {
+        (200..400).contains(&self.status)
+    }
+
+    /// Returns the body, pretty-printed if it is JSON.
+    pub fn pretty_body(&self) -> String {
+        serde_json::from_str::<serde_json::Value>(&self.body)
+            .ok()
+            .and_then(|value| serde_json::to_string_pretty(&value).ok())
+            .unwrap_or_else(|| self.body.clone())
+    }
+}
+
+/// Saved requests of a project.
+#[derive(Debug, Default)]
+pub struct SavedRequests {
+    path: PathBuf,
+    pub requests: Vec<HttpRequest>,
+}
+
+impl SavedRequests {
+    pub fn load(project: &str) -> Self {
+        Self::load_from(
+            get_data_dir()
+                .join(REQUESTS_DIR)
+                .join(format!("{project}.json")),
+        )
+    }
+
+    /// Loads the requests from the given path.
+    ///
+    /// A missing or invalid file results in no requests.
+    pub fn load_from(path: PathBuf) -> Self {
+        let requests = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                log::warn!("Ignoring invalid requests at {}: {e}", path.display());
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self { path, requests }
+    }
+
+    /// Saves a request, replacing the one with the same name.
+    pub fn save(&mut self, request: HttpRequest) -> Result<()> {
+        match self.requests.iter_mut().find(|v| v.name == request.name) {
+            Some(saved) => *saved = request,
+            None => self.requests.push(request),
+        }
+        self.write()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        self.requests.retain(|v| v.name != name);
+        self.write()
+    }
+
+    fn write(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.requests)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::shuttle::Shuttle;
+
+    #[tokio::test]
+    async fn test_send_request() {
+        // Echoes the request line and the body back as JSON.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = String::new();
+            let mut buffer = [0; 4096];
+            // The body might arrive after the head.
+            while !request.ends_with("[1]") {
+                let size = stream.read(&mut buffer).await.unwrap();
+                if size == 0 {
+                    break;
+                }
+                request.push_str(&String::from_utf8_lossy(&buffer[..size]));
+            }
+            let (head, body) = request.split_once("\r\n\r\n").unwrap_or_default();
+            let line = head.lines().next().unwrap_or_default();
+            let token = head
+                .lines()
+                .find_map(|v| v.strip_prefix("x-token: "))
+                .unwrap_or_default();
+            let body = format!(r#"{{"line":"{line}","token":"{token}","body":{body}}}"#);
+            let response = format!(
+                "HTTP/1.1 201 Created\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let request = HttpRequest {
+            name: String::from("create"),
+            method: String::from("POST"),
+            path: String::from("/users?page=2"),
+            headers: parse_headers("x-token: abc\n\n").unwrap(),
+            body: String::from("[1]"),
+        };
+        let shuttle = Shuttle::with_api("http://localhost", None).unwrap();
+        let response = shuttle
+            .send_request(&request.url(&format!("http://{address}/")), &request)
+            .await
+            .unwrap();
+        assert_eq!(
+            (response.status, response.reason.as_str()),
+            (201, "Created")
+        );
+        assert!(response.headers.contains(&(
+            String::from("content-type"),
+            String::from("application/json")
+        )));
+        assert_eq!(
+            response.pretty_body(),
+            "{\n  \"body\": [\n    1\n  ],\n  \"line\": \"POST /users?page=2 HTTP/1.1\",\n  \
+             \"token\": \"abc\"\n}"
+        );
+        assert!(parse_headers("no colon").is_err());
+        assert_eq!(format_headers(&request.headers), "x-token: abc");
+    }
+
+    #[test]
+    fn test_saved_requests() {
+        let path = std::env::temp_dir().join(format!(
+            "shuttle-tui-requests-{}/api.json",
+            std::process::id()
+        ));
+        let request = |name: &str, path: &str| HttpRequest {
+            name: name.to_string(),
+            method: String::from("GET"),
+            path: path.to_string(),
+            ..Default::default()
+        };
+        let mut saved = SavedRequests::load_from(path.clone());
+        saved.save(request("health", "/")).unwrap();
+        saved.save(request("users", "/users")).unwrap();
+        saved.save(request("health", "/health")).unwrap();
+        saved.remove("users").unwrap();
+        assert_eq!(
+            SavedRequests::load_from(path.clone()).requests,
+            [request("health", "/health")]
+        );
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,278 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{bail, Result};
+
+use crate::{template, utils::get_config_dir};
+
+/// Version of the Shuttle crates that the bundled templates depend on.
+pub const SHUTTLE_VERSION: &str = "0.35.0";
+
+/// Directory of the config directory with the local templates.
+const TEMPLATES_DIR: &str = "templates";
+
+/// Directories that are not copied from a local template.
+const IGNORED_DIRS: &[&str] = &[".git", "target"];
+
+const MANIFEST: &str = r#"[package]
+name = "{project}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+{dependencies}shuttle-runtime = "{shuttle_version}"
+tokio = "1.28.2"
+"#;
+
+const GITIGNORE: &str = "/target
+.shuttle-storage
+Secrets*.toml
+";
+
+struct Bundled {
+    name: &'static str,
+    description: &'static str,
+    dependencies: &'static str,
+    main: &'static str,
+}
+
+const BUNDLED: &[Bundled] = &[
+    Bundled {
+        name: "axum",
+        description: "Ergonomic and modular web framework built with Tokio, Tower, and Hyper",
+        dependencies: "axum = \"0.7.2\"\nshuttle-axum = \"{shuttle_version}\"\n",
+        main: include_str!("../templates/axum.rs"),
+    },
+    Bundled {
+        name: "actix-web",
+        description: "Powerful, pragmatic, and extremely fast web framework",
+        dependencies: "actix-web = \"4.3.1\"\nshuttle-actix-web = \"{shuttle_version}\"\n",
+        main: include_str!("../templates/actix-web.rs"),
+    },
+    Bundled {
+        name: "rocket",
+        description: "Web framework with a focus on usability, security, and speed",
+        dependencies: "rocket = \"0.5.0\"\nshuttle-rocket = \"{shuttle_version}\"\n",
+        main: include_str!("../templates/rocket.rs"),
+    },
+    Bundled {
+        name: "poem",
+        description: "Full-featured and easy-to-use web framework",
+        dependencies: "poem = \"1.3.55\"\nshuttle-poem = \"{shuttle_version}\"\n",
+        main: include_str!("../templates/poem.rs"),
+    },
+    Bundled {
+        name: "salvo",
+        description: "Powerful and simplest web server framework",
+        dependencies: "salvo = \"0.63.0\"\nshuttle-salvo = \"{shuttle_version}\"\n",
+        main: include_str!("../templates/salvo.rs"),
+    },
+    Bundled {
+        name: "tower",
+        description: "Modular and reusable components for building robust clients and servers",
+        dependencies: "hyper = { version = \"0.14.26\", features = [\"full\"] }\n\
+                       shuttle-tower = \"{shuttle_version}\"\n\
+                       tower = { version = \"0.4.13\", features = [\"full\"] }\n",
+        main: include_str!("../templates/tower.rs"),
+    },
+    Bundled {
+        name: "serenity",
+        description: "Rust library for the Discord API",
+        dependencies: "anyhow = \"1.0.75\"\n\
+                       serenity = { version = \"0.12.0\", default-features = false, \
+                       features = [\"client\", \"gateway\", \"rustls_backend\", \"model\"] }\n\
+                       shuttle-secrets = \"{shuttle_version}\"\n\
+                       shuttle-serenity = \"{shuttle_version}\"\n\
+                       tracing = \"0.1.37\"\n",
+        main: include_str!("../templates/serenity.rs"),
+    },
+];
+
+/// Template of a new project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    pub name: String,
+    pub description: String,
+    /// Relative paths and contents of the files, with placeholders.
+    files: Vec<(PathBuf, String)>,
+}
+
+impl Template {
+    fn bundled(bundled: &Bundled) -> Self {
+        Self {
+            name: bundled.name.to_string(),
+            description: bundled.description.to_string(),
+            files: vec![
+                (
+                    PathBuf::from("Cargo.toml"),
+                    MANIFEST.replace("{dependencies}", bundled.dependencies),
+                ),
+                (PathBuf::from("src/main.rs"), bundled.main.to_string()),
+                (PathBuf::from(".gitignore"), GITIGNORE.to_string()),
+            ],
+        }
+    }
+
+    /// Loads a template from the files of a directory.
+    fn load(directory: &Path) -> Result<Self> {
+        let mut files = Vec::new();
+        collect_files(directory, directory, &mut files)?;
+        files.sort();
+        Ok(Self {
+            name: directory
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            description: format!("Local template in {}", directory.display()),
+            files,
+        })
+    }
+
+    /// Returns `true` if the name or the description contains the query,
+    /// ignoring the case.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.name.to_lowercase().contains(&query)
+            || self.description.to_lowercase().contains(&query)
+    }
+
+    /// Returns the files of a project with the given name.
+    ///
+    /// The `{project}` and `{shuttle_version}` placeholders are replaced in
+    /// the contents.
+    pub fn render(&self, project: &str) -> Result<Vec<(PathBuf, String)>> {
+        let variables = [
+            ("project", Some(project.to_string())),
+            ("shuttle_version", Some(SHUTTLE_VERSION.to_string())),
+        ];
+        self.files
+            .iter()
+            .map(|(path, content)| Ok((path.clone(), template::render(content, &variables)?)))
+            .collect()
+    }
+}
+
+fn collect_files(root: &Path, directory: &Path, files: &mut Vec<(PathBuf, String)>) -> Result<()> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if !path
+                .file_name()
+                .is_some_and(|name| IGNORED_DIRS.iter().any(|v| name == *v))
+            {
+                collect_files(root, &path, files)?;
+            }
+        } else {
+            let relative = path.strip_prefix(root)?.to_path_buf();
+            files.push((relative, fs::read_to_string(&path)?));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the directory of the local templates.
+pub fn local_directory() -> PathBuf {
+    get_config_dir().join(TEMPLATES_DIR)
+}
+
+/// Returns the bundled templates along with the ones in the subdirectories of
+/// the given directory.
+///
+/// A local template replaces the bundled template of the same name.
+pub fn templates(directory: &Path) -> Vec<Template> {
+    let mut templates = BUNDLED.iter().map(Template::bundled).collect::<Vec<_>>();
+    let mut directories = fs::read_dir(directory)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|v| v.path()))
+                .filter(|path| path.is_dir())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    directories.sort();
+    for directory in directories {
+        match Template::load(&directory) {
+            Ok(template) => match templates.iter_mut().find(|v| v.name == template.name) {
+                Some(bundled) => *bundled = template,
+                None => templates.push(template),
+            },
+            Err(e) => log::error!("Failed to load the template {}: {e}", directory.display()),
+        }
+    }
+    templates
+}
+
+/// Writes the files of a project to the directory.
+///
+/// Nothing is written if any of the files already exists.
+pub fn write(directory: &Path, files: &[(PathBuf, String)]) -> Result<()> {
+    if let Some((path, _)) = files.iter().find(|(path, _)| directory.join(path).exists()) {
+        bail!("{} already exists", directory.join(path).display());
+    }
+    for (path, content) in files {
+        let path = directory.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_templates() {
+        let directory =
+            std::env::temp_dir().join(format!("shuttle-tui-templates-{}", std::process::id()));
+        let local = directory.join("templates");
+        fs::create_dir_all(local.join("axum/src")).unwrap();
+        fs::create_dir_all(local.join("axum/target")).unwrap();
+        fs::write(local.join("axum/src/main.rs"), "// {project}\n").unwrap();
+        fs::write(local.join("axum/target/build"), "").unwrap();
+
+        let templates = templates(&local);
+        assert_eq!(templates.len(), BUNDLED.len());
+        let rocket = templates.iter().find(|v| v.name == "rocket").unwrap();
+        let files = rocket.render("api").unwrap();
+        assert_eq!(
+            files
+                .iter()
+                .map(|(path, _)| path.clone())
+                .collect::<Vec<_>>(),
+            [
+                PathBuf::from("Cargo.toml"),
+                PathBuf::from("src/main.rs"),
+                PathBuf::from(".gitignore")
+            ]
+        );
+        assert!(files[0].1.contains("name = \"api\""));
+        assert!(files[0]
+            .1
+            .contains(&format!("shuttle-rocket = \"{SHUTTLE_VERSION}\"")));
+
+        let axum = templates.iter().find(|v| v.name == "axum").unwrap();
+        assert!(axum.matches("LOCAL"));
+        let files = axum.render("api").unwrap();
+        assert_eq!(
+            files,
+            [(PathBuf::from("src/main.rs"), String::from("// api\n"))]
+        );
+
+        let project = directory.join("api");
+        write(&project, &files).unwrap();
+        assert_eq!(
+            fs::read_to_string(project.join("src/main.rs")).unwrap(),
+            "// api\n"
+        );
+        assert!(write(&project, &files).is_err());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,397 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, FuncArgs, Map, Scope, AST};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action, cache::Snapshot, shuttle::models::DeploymentState, tab::Tab,
+    utils::get_config_dir,
+};
+
+const SCRIPTS_DIR: &str = "scripts";
+
+/// Operations after which a hook is stopped, since hooks run on the UI loop.
+const MAX_OPERATIONS: u64 = 500_000;
+
+/// Depth of the function calls after which a hook is stopped.
+const MAX_CALL_LEVELS: usize = 32;
+
+/// State of the application that the scripts can read.
+#[derive(Debug, Default)]
+struct ScriptState {
+    snapshot: Snapshot,
+    project: Option<String>,
+    tab: Tab,
+    profile: String,
+}
+
+/// Rhai scripts of the config directory that react to events.
+///
+/// A script can define the following hooks:
+///
+/// - `on_start()`
+/// - `on_tab_changed(tab)`
+/// - `on_project_selected(project)`
+/// - `on_deployment_state(project, deployment_id, state)`, when a deployment
+///   appears or changes its state, as seen by the refreshes of the platform
+///   state every `platform.refresh_interval` seconds
+///
+/// and use `dispatch(action)`, `notify(message)` and `copy(text)` along with
+/// functions that read the state, such as `projects()` and `deployments(project)`.
+///
+/// Hooks that run for too long or recurse too deeply are stopped with an error.
+pub struct Scripts {
+    engine: Engine,
+    scripts: Vec<(PathBuf, AST)>,
+    state: Arc<Mutex<ScriptState>>,
+    /// Last known state of every deployment, by ID.
+    deployment_states: Option<HashMap<String, DeploymentState>>,
+    tx: UnboundedSender<Action>,
+}
+
+impl Scripts {
+    pub fn new(tx: UnboundedSender<Action>) -> Self {
+        let state = Arc::new(Mutex::new(ScriptState::default()));
+        Self {
+            engine: engine(Arc::clone(&state), tx.clone()),
+            scripts: Vec::new(),
+            state,
+            deployment_states: None,
+            tx,
+        }
+    }
+
+    /// Returns the directory that the scripts are loaded from.
+    pub fn directory() -> PathBuf {
+        get_config_dir().join(SCRIPTS_DIR)
+    }
+
+    /// Loads the `.rhai` files of the directory, replacing the loaded ones.
+    ///
+    /// Scripts that fail to compile are skipped with an error.
+    pub fn load_from(&mut self, directory: &Path) {
+        let mut paths = fs::read_dir(directory)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|v| v.path()))
+                    .filter(|path| path.extension().is_some_and(|v| v == "rhai"))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        paths.sort();
+        self.scripts.clear();
+        for path in paths {
+            let ast = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|script| self.engine.compile(script).map_err(|e| e.to_string()));
+            match ast {
+                Ok(ast) => self.scripts.push((path, ast)),
+                Err(e) => self.error(&path, e),
+            }
+        }
+        log::info!("Loaded {} scripts", self.scripts.len());
+    }
+
+    /// Updates the state that the scripts can read and runs the hooks of the
+    /// action.
+    pub fn update(&mut self, action: &Action, tab: Tab) {
+        let tab_changed = {
+            let mut state = self.state();
+            let changed = state.tab != tab;
+            state.tab = tab;
+            match action {
+                Action::PlatformUpdated(snapshot) => state.snapshot = *snapshot.clone(),
+                Action::SelectProject(project) => state.project = Some(project.clone()),
+                Action::ProfileChanged(profile) => state.profile = profile.clone(),
+                _ => {}
+            }
+            changed
+        };
+        if tab_changed {
+            self.call("on_tab_changed", (tab.to_string(),));
+        }
+        match action {
+            Action::SelectProject(project) => {
+                self.call("on_project_selected", (project.clone(),));
+            }
+            Action::PlatformUpdated(snapshot) => self.compare_deployments(snapshot),
+            _ => {}
+        }
+    }
+
+    /// Sets the initial state and runs the `on_start` hooks.
+    pub fn start(&mut self, profile: &str, snapshot: &Snapshot) {
+        self.update(&Action::ProfileChanged(profile.to_string()), Tab::default());
+        self.update(
+            &Action::PlatformUpdated(Box::new(snapshot.clone())),
+            Tab::default(),
+        );
+        self.call("on_start", ());
+    }
+
+    fn compare_deployments(&mut self, snapshot: &Snapshot) {
+        let states = snapshot
+            .state
+            .deployments
+            .values()
+            .flatten()
+            .map(|v| (v.id.clone(), v.state))
+            .collect::<HashMap<_, _>>();
+        if let Some(previous) = self.deployment_states.replace(states) {
+            for (project, deployments) in &snapshot.state.deployments {
+                for deployment in deployments {
+                    if previous.get(&deployment.id) != Some(&deployment.state) {
+                        self.call(
+                            "on_deployment_state",
+                            (
+                                project.clone(),
+                                deployment.id.clone(),
+                                deployment.state.to_string(),
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Calls the hook in every script that defines it.
+    fn call(&self, hook: &str, args: impl FuncArgs + Clone) {
+        for (path, ast) in &self.scripts {
+            if !ast.iter_functions().any(|v| v.name == hook) {
+                continue;
+            }
+            let result = self.engine.call_fn_with_options::<Dynamic>(
+                CallFnOptions::new().eval_ast(false),
+                &mut Scope::new(),
+                ast,
+                hook,
+                args.clone(),
+            );
+            if let Err(e) = result {
+                self.error(path, e.to_string());
+            }
+        }
+    }
+
+    fn error(&self, path: &Path, e: String) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        log::error!("Script {name} failed: {e}");
+        let _ = self.tx.send(Action::Error(format!("{name}: {e}")));
+    }
+
+    fn state(&self) -> MutexGuard<'_, ScriptState> {
+        lock(&self.state)
+    }
+}
+
+fn lock(state: &Mutex<ScriptState>) -> MutexGuard<'_, ScriptState> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Returns an engine with the functions that the scripts can use.
+fn engine(state: Arc<Mutex<ScriptState>>, tx: UnboundedSender<Action>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.on_print(|text| log::info!("Script: {text}"));
+    engine.on_debug(|text, _, _| log::debug!("Script: {text}"));
+
+    let sender = tx.clone();
+    engine.register_fn(
+        "dispatch",
+        move |action: &str| -> Result<(), Box<EvalAltResult>> {
+            let action = action
+                .parse::<Action>()
+                .map_err(|e| format!("invalid action `{action}`: {e}"))?;
+            let _ = sender.send(action);
+            Ok(())
+        },
+    );
+    let sender = tx;
+    engine.register_fn("notify", move |message: &str| {
+        let _ = sender.send(Action::Notify(message.to_string()));
+    });
+    engine.register_fn("copy", |text: &str| -> Result<(), Box<EvalAltResult>> {
+        copy_to_clipboard(text).map_err(|e| format!("failed to copy: {e}").into())
+    });
+
+    let shared = Arc::clone(&state);
+    engine.register_fn("current_tab", move || lock(&shared).tab.to_string());
+    let shared = Arc::clone(&state);
+    engine.register_fn("profile", move || lock(&shared).profile.clone());
+    let shared = Arc::clone(&state);
+    engine.register_fn("selected_project", move || {
+        lock(&shared)
+            .project
+            .clone()
+            .map(Dynamic::from)
+            .unwrap_or(Dynamic::UNIT)
+    });
+    let shared = Arc::clone(&state);
+    engine.register_fn("projects", move || {
+        lock(&shared)
+            .snapshot
+            .state
+            .projects
+            .iter()
+            .map(|project| {
+                let mut map = Map::new();
+                map.insert("name".into(), project.name.clone().into());
+                map.insert("state".into(), project.state.to_string().into());
+                Dynamic::from_map(map)
+            })
+            .collect::<Array>()
+    });
+    let shared = state;
+    engine.register_fn("deployments", move |project: &str| {
+        lock(&shared)
+            .snapshot
+            .state
+            .deployments(project)
+            .iter()
+            .map(|deployment| {
+                let mut map = Map::new();
+                map.insert("id".into(), deployment.id.clone().into());
+                map.insert("state".into(), deployment.state.to_string().into());
+                map.insert(
+                    "last_update".into(),
+                    deployment.last_update.to_rfc3339().into(),
+                );
+                map.insert(
+                    "branch".into(),
+                    deployment
+                        .git_branch
+                        .clone()
+                        .map(Dynamic::from)
+                        .unwrap_or(Dynamic::UNIT),
+                );
+                Dynamic::from_map(map)
+            })
+            .collect::<Array>()
+    });
+    engine
+}
+
+/// Copies the text to the clipboard with the OSC 52 escape sequence, which
+/// also works over SSH in the terminals that support it.
+///
+/// It is written to stderr, which the terminal is drawn on.
+fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stderr = io::stderr();
+    write!(stderr, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stderr.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::{
+        cache::PlatformState,
+        shuttle::models::{Deployment, Project, ProjectState},
+    };
+
+    fn snapshot(state: DeploymentState) -> Snapshot {
+        let deployment: Deployment = serde_json::from_value(serde_json::json!({
+            "id": "d1",
+            "state": state,
+            "last_update": "2023-10-01T12:00:00Z",
+        }))
+        .unwrap();
+        Snapshot {
+            state: PlatformState {
+                projects: vec![Project {
+                    id: String::new(),
+                    name: String::from("api"),
+                    state: ProjectState::Ready,
+                    idle_minutes: None,
+                }],
+                deployments: [(String::from("api"), vec![deployment])].into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_hooks() {
+        let directory =
+            std::env::temp_dir().join(format!("shuttle-tui-scripts-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(
+            directory.join("crashed.rhai"),
+            r#"
+                fn on_start() {
+                    for project in projects() {
+                        dispatch(`SelectProject(${project.name})`);
+                    }
+                }
+
+                fn on_deployment_state(project, id, state) {
+                    if state == "crashed" {
+                        notify(`${project} crashed with ${deployments(project).len()} deployment`);
+                    }
+                }
+            "#,
+        )
+        .unwrap();
+        fs::write(directory.join("broken.rhai"), "fn on_start( {").unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut scripts = Scripts::new(tx);
+        scripts.load_from(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(matches!(rx.try_recv(), Ok(Action::Error(e)) if e.starts_with("broken.rhai: ")));
+        scripts.start("default", &snapshot(DeploymentState::Running));
+        assert_eq!(
+            rx.try_recv(),
+            Ok(Action::SelectProject(String::from("api")))
+        );
+
+        let running = Action::PlatformUpdated(Box::new(snapshot(DeploymentState::Running)));
+        scripts.update(&running, Tab::Home);
+        assert!(rx.try_recv().is_err());
+        let crashed = Action::PlatformUpdated(Box::new(snapshot(DeploymentState::Crashed)));
+        scripts.update(&crashed, Tab::Home);
+        assert_eq!(
+            rx.try_recv(),
+            Ok(Action::Notify(String::from(
+                "api crashed with 1 deployment"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let directory =
+            std::env::temp_dir().join(format!("shuttle-tui-limits-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("loop.rhai"), "fn on_start() { loop {} }").unwrap();
+        fs::write(
+            directory.join("recursion.rhai"),
+            "fn down(n) { down(n + 1) } fn on_start() { down(0) }",
+        )
+        .unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut scripts = Scripts::new(tx);
+        scripts.load_from(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+
+        scripts.start("default", &Snapshot::default());
+        for name in ["loop.rhai", "recursion.rhai"] {
+            assert!(matches!(rx.try_recv(), Ok(Action::Error(e)) if e.starts_with(name)));
+        }
+    }
+}
@@ -1 +1,360 @@
-pub struct Shuttle;
+use std::{
+    env, fs,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::{eyre, Result};
+use directories::ProjectDirs;
+use reqwest::{Client, RequestBuilder, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use chrono::Utc;
+
+use self::models::{Certificate, Deployment, HealthCheck, LogItem, Project, Resource, Service};
+use crate::{
+    config::Config,
+    request::{HttpRequest, HttpResponse},
+};
+
+pub mod models;
+
+/// Default URL of the Shuttle API.
+pub const DEFAULT_API_URL: &str = "https://api.shuttle.rs";
+
+/// Name of the profile that is used when no profile is selected.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Time after which a request is given up.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time after which a health check counts as failed.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Client for the Shuttle API.
+#[derive(Clone, Debug)]
+pub struct Shuttle {
+    client: Client,
+    profile: String,
+    api_url: String,
+    api_key: Option<String>,
+}
+
+/// Error that is returned by the API.
+#[derive(Debug, Deserialize)]
+struct ApiError {
+    message: String,
+}
+
+impl Shuttle {
+    /// Creates a new client.
+    ///
+    /// The API URL and key are read from `SHUTTLE_API` and `SHUTTLE_API_KEY`
+    /// respectively, falling back to the key that is stored by `cargo shuttle login`.
+    pub fn new() -> Result<Self> {
+        let api_url = env::var("SHUTTLE_API").unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+        let api_key = env::var("SHUTTLE_API_KEY")
+            .ok()
+            .or_else(read_cargo_shuttle_api_key);
+        Self::with_api(api_url, api_key)
+    }
+
+    /// Creates a client for a profile of the configuration.
+    ///
+    /// If no name is given, the default profile of the configuration is used.
+    /// Without any configured profile, this is the same as [`Shuttle::new`].
+    pub fn from_profile(config: &Config, name: Option<&str>) -> Result<Self> {
+        let Some(name) = name.or(config.default_profile.as_deref()) else {
+            return Self::new();
+        };
+        let Some(profile) = config.profiles.get(name) else {
+            if name == DEFAULT_PROFILE {
+                return Self::new();
+            }
+            return Err(eyre!("Unknown profile: {name}"));
+        };
+        let api_url = profile
+            .api_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+        let api_key = match &profile.api_key {
+            Some(reference) => Some(resolve_api_key(reference)?),
+            None => env::var("SHUTTLE_API_KEY")
+                .ok()
+                .or_else(read_cargo_shuttle_api_key),
+        };
+        let mut shuttle = Self::with_api(api_url, api_key)?;
+        shuttle.profile = name.to_string();
+        Ok(shuttle)
+    }
+
+    pub fn with_api(api_url: impl Into<String>, api_key: Option<String>) -> Result<Self> {
+        let client = Client::builder()
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            profile: DEFAULT_PROFILE.to_string(),
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            api_key,
+        })
+    }
+
+    /// Returns the name of the profile that the client was created for.
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    pub fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.api_key.is_some()
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}{}", self.api_url, path));
+        match &self.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    async fn send(request: RequestBuilder) -> Result<Response> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<ApiError>(&body)
+            .map(|e| e.message)
+            .unwrap_or(body);
+        Err(eyre!("API request failed ({status}): {message}"))
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let response = Self::send(self.request(reqwest::Method::GET, path)).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn post<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let response = Self::send(self.request(reqwest::Method::POST, path)).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn post_json<T: DeserializeOwned>(&self, path: &str, body: &impl Serialize) -> Result<T> {
+        let response = Self::send(self.request(reqwest::Method::POST, path).json(body)).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Returns the version of the platform.
+    pub async fn get_version(&self) -> Result<String> {
+        let response = Self::send(self.request(reqwest::Method::GET, "/version")).await?;
+        Ok(response.text().await?.trim().trim_matches('"').to_string())
+    }
+
+    pub async fn get_projects(&self) -> Result<Vec<Project>> {
+        self.get("/projects").await
+    }
+
+    pub async fn get_project(&self, project: &str) -> Result<Project> {
+        self.get(&format!("/projects/{project}")).await
+    }
+
+    /// Creates a project, which is then provisioned by the platform.
+    pub async fn create_project(&self, project: &str, idle_minutes: u64) -> Result<Project> {
+        self.post_json(
+            &format!("/projects/{project}"),
+            &serde_json::json!({ "idle_minutes": idle_minutes }),
+        )
+        .await
+    }
+
+    pub async fn get_service(&self, project: &str) -> Result<Service> {
+        self.get(&format!("/projects/{project}/services/{project}"))
+            .await
+    }
+
+    pub async fn get_deployments(&self, project: &str) -> Result<Vec<Deployment>> {
+        self.get(&format!("/projects/{project}/deployments?page=0&limit=50"))
+            .await
+    }
+
+    pub async fn get_logs(&self, project: &str, deployment_id: &str) -> Result<Vec<LogItem>> {
+        self.get(&format!(
+            "/projects/{project}/deployments/{deployment_id}/logs"
+        ))
+        .await
+    }
+
+    /// Probes the given endpoint of a service.
+    pub async fn check_health(&self, endpoint: &str) -> HealthCheck {
+        let start = Instant::now();
+        let response = self
+            .client
+            .get(endpoint)
+            .timeout(HEALTH_CHECK_TIMEOUT)
+            .send()
+            .await;
+        let latency_ms = start.elapsed().as_millis() as u64;
+        let (status, error) = match response {
+            Ok(response) => (Some(response.status().as_u16()), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        HealthCheck {
+            endpoint: endpoint.to_string(),
+            checked_at: Utc::now(),
+            status,
+            latency_ms,
+            error,
+        }
+    }
+
+    /// Sends a request to the given URL of a service.
+    pub async fn send_request(&self, url: &str, request: &HttpRequest) -> Result<HttpResponse> {
+        let method = reqwest::Method::from_bytes(request.method.to_uppercase().as_bytes())?;
+        let mut builder = self.client.request(method, url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if !request.body.is_empty() {
+            builder = builder.body(request.body.clone());
+        }
+        let start = Instant::now();
+        let response = builder.send().await?;
+        let status = response.status();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).to_string(),
+                )
+            })
+            .collect();
+        let body = response.text().await?;
+        Ok(HttpResponse {
+            status: status.as_u16(),
+            reason: status.canonical_reason().unwrap_or_default().to_string(),
+            elapsed: start.elapsed(),
+            headers,
+            body,
+        })
+    }
+
+    pub async fn get_resources(&self, project: &str) -> Result<Vec<Resource>> {
+        self.get(&format!("/projects/{project}/services/{project}/resources"))
+            .await
+    }
+
+    /// Returns the certificates of the custom domains of a project.
+    pub async fn get_certificates(&self, project: &str) -> Result<Vec<Certificate>> {
+        self.get(&format!("/projects/{project}/certificates")).await
+    }
+
+    /// Adds a custom domain to a project, which gets a certificate once its
+    /// DNS record points to the project.
+    pub async fn add_certificate(&self, project: &str, domain: &str) -> Result<Certificate> {
+        self.post_json(
+            &format!("/projects/{project}/certificates"),
+            &serde_json::json!({ "subject": domain }),
+        )
+        .await
+    }
+
+    /// Removes a custom domain and its certificate from a project.
+    pub async fn delete_certificate(&self, project: &str, domain: &str) -> Result<()> {
+        let request = self
+            .request(
+                reqwest::Method::DELETE,
+                &format!("/projects/{project}/certificates"),
+            )
+            .json(&serde_json::json!({ "subject": domain }));
+        Self::send(request).await?;
+        Ok(())
+    }
+
+    /// Redeploys the build of an existing deployment and returns the new deployment.
+    pub async fn redeploy(&self, project: &str, deployment_id: &str) -> Result<Deployment> {
+        self.post(&format!(
+            "/projects/{project}/deployments/{deployment_id}/redeploy"
+        ))
+        .await
+    }
+}
+
+/// Resolves the API key reference of a profile.
+fn resolve_api_key(reference: &str) -> Result<String> {
+    if let Some(variable) = reference.strip_prefix("env:") {
+        env::var(variable).map_err(|_| eyre!("Environment variable {variable} is not set"))
+    } else if let Some(path) = reference.strip_prefix("file:") {
+        Ok(fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read the API key from {path}: {e}"))?
+            .trim()
+            .to_string())
+    } else if reference == "cargo-shuttle" {
+        read_cargo_shuttle_api_key()
+            .ok_or_else(|| eyre!("No API key found, run `cargo shuttle login` first"))
+    } else {
+        Ok(reference.to_string())
+    }
+}
+
+/// Reads the API key from the configuration of `cargo-shuttle`.
+fn read_cargo_shuttle_api_key() -> Option<String> {
+    let path = ProjectDirs::from("", "", "shuttle")?
+        .config_dir()
+        .join("config.toml");
+    config::Config::builder()
+        .add_source(config::File::from(path).required(false))
+        .build()
+        .ok()?
+        .get_string("api_key")
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::config::Profile;
+
+    #[test]
+    fn test_resolve_api_key() {
+        assert_eq!(resolve_api_key("secret").unwrap(), "secret");
+        env::set_var("SHUTTLE_TUI_TEST_API_KEY", "from-env");
+        assert_eq!(
+            resolve_api_key("env:SHUTTLE_TUI_TEST_API_KEY").unwrap(),
+            "from-env"
+        );
+        assert!(resolve_api_key("env:SHUTTLE_TUI_TEST_MISSING").is_err());
+        assert!(resolve_api_key("file:/nonexistent").is_err());
+    }
+
+    #[test]
+    fn test_from_profile() {
+        let mut config = Config::default();
+        config.profiles.insert(
+            String::from("local"),
+            Profile {
+                api_url: Some(String::from("http://localhost:8001/")),
+                api_key: Some(String::from("key")),
+            },
+        );
+        let shuttle = Shuttle::from_profile(&config, Some("local")).unwrap();
+        assert_eq!(shuttle.profile(), "local");
+        assert_eq!(shuttle.api_url(), "http://localhost:8001");
+        assert!(shuttle.is_authenticated());
+        assert!(Shuttle::from_profile(&config, Some("unknown")).is_err());
+    }
+}
@@ -1,6 +1,6 @@
-use enum_iterator::Sequence;
+use enum_iterator::{all, next_cycle, previous_cycle, Sequence};
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Sequence)]
 pub enum Tab {
@@ -8,6 +8,42 @@ pub enum Tab {
     Home,
     Projects,
     Deployments,
+    Logs,
+    Local,
+    Dashboard,
+}
+
+impl Tab {
+    /// Returns `true` for views that are only opened from other tabs, such as
+    /// the logs of a deployment, which are left out of the tab bar.
+    pub fn is_view_only(&self) -> bool {
+        matches!(self, Tab::Logs)
+    }
+
+    /// Returns the tabs of the tab bar, in order.
+    pub fn tabs() -> impl Iterator<Item = Tab> {
+        all::<Tab>().filter(|tab| !tab.is_view_only())
+    }
+
+    /// Returns the next tab of the tab bar, wrapping around.
+    pub fn next(&self) -> Tab {
+        self.cycle(next_cycle)
+    }
+
+    /// Returns the previous tab of the tab bar, wrapping around.
+    pub fn previous(&self) -> Tab {
+        self.cycle(previous_cycle)
+    }
+
+    fn cycle(&self, step: fn(&Tab) -> Option<Tab>) -> Tab {
+        let mut tab = *self;
+        loop {
+            tab = step(&tab).unwrap_or_default();
+            if !tab.is_view_only() {
+                return tab;
+            }
+        }
+    }
 }
 
 impl fmt::Display for Tab {
@@ -15,3 +51,33 @@ impl fmt::Display for Tab {
         write!(f, "{}", format!("{:?}", self))
     }
 }
+
+impl FromStr for Tab {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        all::<Tab>()
+            .find(|tab| tab.to_string() == s)
+            .ok_or_else(|| {
+                let names = all::<Tab>().map(|tab| tab.to_string()).collect::<Vec<_>>();
+                format!("expected one of {}", names.join(", "))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_cycle() {
+        assert_eq!(Tab::Deployments.next(), Tab::Local);
+        assert_eq!(Tab::Local.previous(), Tab::Deployments);
+        assert_eq!(Tab::Logs.next(), Tab::Local);
+        assert_eq!(Tab::Dashboard.next(), Tab::Home);
+        assert_eq!(Tab::Home.previous(), Tab::Dashboard);
+        assert!(!Tab::tabs().any(|tab| tab == Tab::Logs));
+    }
+}
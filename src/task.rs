@@ -0,0 +1,305 @@
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::Result;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+use crate::action::Action;
+
+/// Identifier of a task that is tracked by the [`TaskManager`].
+pub type TaskId = usize;
+
+/// Snapshot of a running task.
+#[derive(Clone, Debug)]
+pub struct TaskInfo {
+    pub id: TaskId,
+    pub name: String,
+    /// Completion ratio between `0.0` and `1.0`, if known.
+    pub progress: Option<f64>,
+    pub message: String,
+    pub started_at: Instant,
+    /// Whether the task runs on its own rather than for the user.
+    background: bool,
+    cancellation_token: CancellationToken,
+}
+
+impl TaskInfo {
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_token.is_cancelled()
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    next_id: TaskId,
+    tasks: BTreeMap<TaskId, TaskInfo>,
+    action_tx: Option<UnboundedSender<Action>>,
+}
+
+impl Registry {
+    /// Returns the number of tasks that are not in the background.
+    fn foreground(&self) -> usize {
+        self.tasks.values().filter(|task| !task.background).count()
+    }
+
+    fn send(&self, action: Action) {
+        if let Some(tx) = &self.action_tx {
+            if let Err(e) = tx.send(action) {
+                log::error!("Failed to send task action: {e}");
+            }
+        }
+    }
+}
+
+/// Registry of long-running operations that are spawned on tokio.
+///
+/// The manager is cheap to clone and every clone refers to the same set of
+/// tasks. Completion and failure are reported back as actions, while the
+/// progress is kept in the registry so that it can be rendered at any time.
+#[derive(Clone, Default)]
+pub struct TaskManager {
+    registry: Arc<Mutex<Registry>>,
+}
+
+impl TaskManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register_action_handler(&self, tx: UnboundedSender<Action>) {
+        self.lock().action_tx = Some(tx);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Registry> {
+        self.registry
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Spawns a new task.
+    ///
+    /// `Action::EnterProcessing` is sent when the first task starts and
+    /// `Action::ExitProcessing` when the last one finishes.
+    pub fn spawn<F, Fut>(&self, name: impl Into<String>, f: F) -> TaskId
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.spawn_task(name, false, f)
+    }
+
+    /// Spawns a task that runs periodically on its own, such as a probe.
+    ///
+    /// Background tasks report their completion like any other task, but are
+    /// left out of [`TaskManager::list`] and [`TaskManager::is_busy`] and do
+    /// not enter the processing state.
+    pub fn spawn_background<F, Fut>(&self, name: impl Into<String>, f: F) -> TaskId
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.spawn_task(name, true, f)
+    }
+
+    /// Spawns a task that is either in the foreground, as with
+    /// [`TaskManager::spawn`], or in the background.
+    pub fn spawn_task<F, Fut>(&self, name: impl Into<String>, background: bool, f: F) -> TaskId
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let cancellation_token = CancellationToken::new();
+        let id = {
+            let mut registry = self.lock();
+            let id = registry.next_id;
+            registry.next_id += 1;
+            registry.tasks.insert(
+                id,
+                TaskInfo {
+                    id,
+                    name: name.into(),
+                    progress: None,
+                    message: String::new(),
+                    started_at: Instant::now(),
+                    background,
+                    cancellation_token: cancellation_token.clone(),
+                },
+            );
+            if !background && registry.foreground() == 1 {
+                registry.send(Action::EnterProcessing);
+            }
+            id
+        };
+        let future = f(TaskContext {
+            id,
+            cancellation_token: cancellation_token.clone(),
+            manager: self.clone(),
+        });
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let action = tokio::select! {
+                _ = cancellation_token.cancelled() => Action::TaskCancelled(id),
+                result = future => match result {
+                    Ok(()) => Action::TaskCompleted(id),
+                    Err(e) => Action::TaskFailed(id, e.to_string()),
+                },
+            };
+            let mut registry = manager.lock();
+            registry.tasks.remove(&id);
+            registry.send(action);
+            if !background && registry.foreground() == 0 {
+                registry.send(Action::ExitProcessing);
+            }
+        });
+        id
+    }
+
+    /// Requests the cancellation of the given task.
+    ///
+    /// Returns `false` if there is no such task.
+    pub fn cancel(&self, id: TaskId) -> bool {
+        match self.lock().tasks.get(&id) {
+            Some(task) => {
+                task.cancellation_token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn cancel_all(&self) {
+        for task in self.lock().tasks.values() {
+            task.cancellation_token.cancel();
+        }
+    }
+
+    /// Returns the tasks that are not in the background.
+    pub fn list(&self) -> Vec<TaskInfo> {
+        self.lock()
+            .tasks
+            .values()
+            .filter(|task| !task.background)
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.lock().foreground() > 0
+    }
+
+    fn set_progress(&self, id: TaskId, progress: Option<f64>, message: String) {
+        if let Some(task) = self.lock().tasks.get_mut(&id) {
+            task.progress = progress.map(|v| v.clamp(0.0, 1.0));
+            task.message = message;
+        }
+    }
+}
+
+/// Handle that is passed to a spawned task.
+#[derive(Clone)]
+pub struct TaskContext {
+    pub id: TaskId,
+    cancellation_token: CancellationToken,
+    manager: TaskManager,
+}
+
+impl TaskContext {
+    /// Updates the progress of the task.
+    pub fn progress(&self, progress: Option<f64>, message: impl Into<String>) {
+        self.manager.set_progress(self.id, progress, message.into());
+    }
+
+    /// Returns the token that is cancelled when the task is cancelled.
+    ///
+    /// Tasks are dropped at their next await point on cancellation, so this
+    /// is only needed for passing the token along to nested operations.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_token.is_cancelled()
+    }
+
+    /// Sends an action to the application.
+    pub fn send(&self, action: Action) {
+        self.manager.lock().send(action);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::eyre;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_task_completion() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let manager = TaskManager::new();
+        manager.register_action_handler(tx);
+        let id = manager.spawn("test", |ctx| async move {
+            ctx.progress(Some(0.5), "halfway");
+            Ok(())
+        });
+        assert_eq!(rx.recv().await, Some(Action::EnterProcessing));
+        assert_eq!(rx.recv().await, Some(Action::TaskCompleted(id)));
+        assert_eq!(rx.recv().await, Some(Action::ExitProcessing));
+        assert!(!manager.is_busy());
+    }
+
+    #[tokio::test]
+    async fn test_task_failure() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let manager = TaskManager::new();
+        manager.register_action_handler(tx);
+        let id = manager.spawn("test", |_| async { Err(eyre!("boom")) });
+        assert_eq!(rx.recv().await, Some(Action::EnterProcessing));
+        assert_eq!(
+            rx.recv().await,
+            Some(Action::TaskFailed(id, String::from("boom")))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_task_cancellation() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let manager = TaskManager::new();
+        manager.register_action_handler(tx);
+        let id = manager.spawn("test", |_| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        });
+        assert_eq!(manager.list().len(), 1);
+        assert!(manager.cancel(id));
+        assert_eq!(rx.recv().await, Some(Action::EnterProcessing));
+        assert_eq!(rx.recv().await, Some(Action::TaskCancelled(id)));
+        assert!(!manager.cancel(id));
+    }
+
+    #[tokio::test]
+    async fn test_background_task() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let manager = TaskManager::new();
+        manager.register_action_handler(tx);
+        let id = manager.spawn_background("probe", |_| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        });
+        assert!(!manager.is_busy());
+        assert!(manager.list().is_empty());
+        assert!(manager.cancel(id));
+        assert_eq!(rx.recv().await, Some(Action::TaskCancelled(id)));
+    }
+}
@@ -0,0 +1,74 @@
+use color_eyre::eyre::{eyre, Result};
+
+/// Replaces the `{name}` placeholders of the variables in the template.
+///
+/// Placeholders of unknown names are left as they are, so that the braces of
+/// shell commands such as `${HOME}` keep working. A variable without a value
+/// is an error.
+pub fn render(template: &str, variables: &[(&str, Option<String>)]) -> Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let variable = rest.find('}').and_then(|end| {
+            let name = &rest[1..end];
+            variables
+                .iter()
+                .find(|(v, _)| *v == name)
+                .map(|(_, value)| (name, value, end))
+        });
+        match variable {
+            Some((_, Some(value), end)) => {
+                output.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            Some((name, None, _)) => return Err(eyre!("`{{{name}}}` is not available")),
+            None => {
+                output.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Quotes a value for a POSIX shell, so that it is passed as a single word.
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let variables = [
+            ("project", Some(String::from("api"))),
+            ("service_url", None),
+        ];
+        assert_eq!(
+            render("cargo shuttle logs --name {project} ${HOME} {x", &variables).unwrap(),
+            "cargo shuttle logs --name api ${HOME} {x"
+        );
+        assert_eq!(
+            render("curl {service_url}", &variables)
+                .unwrap_err()
+                .to_string(),
+            "`{service_url}` is not available"
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("api"), "'api'");
+        assert_eq!(
+            shell_quote("https://x.dev/$(id)'; id #"),
+            r"'https://x.dev/$(id)'\''; id #'"
+        );
+    }
+}
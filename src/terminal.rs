@@ -0,0 +1,282 @@
+use std::{
+    io::{Read, Write},
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard},
+    thread,
+};
+
+use color_eyre::eyre::{eyre, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::action::Action;
+
+/// Number of lines that are kept above the visible screen.
+const SCROLLBACK: usize = 1000;
+
+/// Shell session that runs in a pseudo-terminal.
+///
+/// The output of the shell is fed into a VT100 emulator on a background
+/// thread, and the screen of the emulator is rendered with [`Screen`].
+pub struct Terminal {
+    parser: Arc<Mutex<vt100::Parser>>,
+    master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+    size: (u16, u16),
+}
+
+impl Terminal {
+    /// Spawns the shell of the user in the given directory.
+    ///
+    /// `Action::TerminalExited` is sent once the shell exits.
+    pub fn spawn(cwd: &Path, rows: u16, cols: u16, tx: UnboundedSender<Action>) -> Result<Self> {
+        let pair = native_pty_system()
+            .openpty(PtySize {
+                rows,
+                cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| eyre!("Failed to open a pseudo-terminal: {e}"))?;
+        let mut command = CommandBuilder::new_default_prog();
+        command.cwd(cwd);
+        command.env("TERM", "xterm-256color");
+        let child = pair
+            .slave
+            .spawn_command(command)
+            .map_err(|e| eyre!("Failed to spawn the shell: {e}"))?;
+        let mut reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| eyre!("Failed to read from the pseudo-terminal: {e}"))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|e| eyre!("Failed to write to the pseudo-terminal: {e}"))?;
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, SCROLLBACK)));
+        let output = Arc::clone(&parser);
+        thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            loop {
+                match reader.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => {
+                        output
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner())
+                            .process(&buffer[..n]);
+                    }
+                }
+            }
+            let _ = tx.send(Action::TerminalExited);
+        });
+        Ok(Self {
+            parser,
+            master: pair.master,
+            writer,
+            child,
+            size: (rows, cols),
+        })
+    }
+
+    /// Sends the key to the shell.
+    pub fn send_key(&mut self, key: KeyEvent) -> Result<()> {
+        let application_cursor = self.parser().screen().application_cursor();
+        if let Some(bytes) = key_to_bytes(key, application_cursor) {
+            self.writer.write_all(&bytes)?;
+            self.writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Resizes the pseudo-terminal and the emulator, if the size changed.
+    pub fn resize(&mut self, rows: u16, cols: u16) -> Result<()> {
+        if self.size == (rows, cols) || rows == 0 || cols == 0 {
+            return Ok(());
+        }
+        self.size = (rows, cols);
+        self.master
+            .resize(PtySize {
+                rows,
+                cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| eyre!("Failed to resize the pseudo-terminal: {e}"))?;
+        self.parser().set_size(rows, cols);
+        Ok(())
+    }
+
+    pub fn parser(&self) -> MutexGuard<'_, vt100::Parser> {
+        self.parser
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        if let Err(e) = self.child.kill() {
+            log::debug!("Failed to kill the shell: {e}");
+        }
+    }
+}
+
+/// Widget that renders the screen of a VT100 emulator.
+pub struct Screen<'a>(pub &'a vt100::Screen);
+
+impl Widget for Screen<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (rows, cols) = self.0.size();
+        for row in 0..rows.min(area.height) {
+            for col in 0..cols.min(area.width) {
+                let Some(cell) = self.0.cell(row, col) else {
+                    continue;
+                };
+                if cell.is_wide_continuation() {
+                    continue;
+                }
+                let mut style = Style::default()
+                    .fg(convert_color(cell.fgcolor()))
+                    .bg(convert_color(cell.bgcolor()));
+                for (enabled, modifier) in [
+                    (cell.bold(), Modifier::BOLD),
+                    (cell.italic(), Modifier::ITALIC),
+                    (cell.underline(), Modifier::UNDERLINED),
+                    (cell.inverse(), Modifier::REVERSED),
+                ] {
+                    if enabled {
+                        style = style.add_modifier(modifier);
+                    }
+                }
+                let contents = cell.contents();
+                let symbol = if contents.is_empty() { " " } else { &contents };
+                buf.get_mut(area.x + col, area.y + row)
+                    .set_symbol(symbol)
+                    .set_style(style);
+            }
+        }
+    }
+}
+
+fn convert_color(color: vt100::Color) -> Color {
+    match color {
+        vt100::Color::Default => Color::Reset,
+        vt100::Color::Idx(index) => Color::Indexed(index),
+        vt100::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
+    }
+}
+
+/// Encodes a key as the bytes that a terminal would send for it.
+///
+/// In application cursor mode the arrow keys are sent as `SS3` sequences.
+pub fn key_to_bytes(key: KeyEvent, application_cursor: bool) -> Option<Vec<u8>> {
+    let cursor = |c: char| {
+        let prefix = if application_cursor { "\x1bO" } else { "\x1b[" };
+        format!("{prefix}{c}").into_bytes()
+    };
+    let bytes = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            let c = c.to_ascii_lowercase();
+            match c {
+                'a'..='z' => vec![c as u8 - b'a' + 1],
+                '@' | ' ' => vec![0],
+                '[' => vec![0x1b],
+                '\\' => vec![0x1c],
+                ']' => vec![0x1d],
+                _ => return None,
+            }
+        }
+        KeyCode::Char(c) => {
+            let mut bytes = Vec::new();
+            if key.modifiers.contains(KeyModifiers::ALT) {
+                bytes.push(0x1b);
+            }
+            bytes.extend(c.to_string().into_bytes());
+            bytes
+        }
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => cursor('A'),
+        KeyCode::Down => cursor('B'),
+        KeyCode::Right => cursor('C'),
+        KeyCode::Left => cursor('D'),
+        KeyCode::Home => cursor('H'),
+        KeyCode::End => cursor('F'),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::F(n @ 1..=4) => format!("\x1bO{}", (b'P' + n - 1) as char).into_bytes(),
+        KeyCode::F(n @ 5..=12) => {
+            let code = [15, 17, 18, 19, 20, 21, 23, 24][n as usize - 5];
+            format!("\x1b[{code}~").into_bytes()
+        }
+        _ => return None,
+    };
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_key_to_bytes() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        assert_eq!(
+            key_to_bytes(key(KeyCode::Char('é'), KeyModifiers::NONE), false),
+            Some("é".as_bytes().to_vec())
+        );
+        assert_eq!(
+            key_to_bytes(key(KeyCode::Char('c'), KeyModifiers::CONTROL), false),
+            Some(vec![3])
+        );
+        assert_eq!(
+            key_to_bytes(key(KeyCode::Char('b'), KeyModifiers::ALT), false),
+            Some(b"\x1bb".to_vec())
+        );
+        assert_eq!(
+            key_to_bytes(key(KeyCode::Up, KeyModifiers::NONE), false),
+            Some(b"\x1b[A".to_vec())
+        );
+        assert_eq!(
+            key_to_bytes(key(KeyCode::Up, KeyModifiers::NONE), true),
+            Some(b"\x1bOA".to_vec())
+        );
+        assert_eq!(
+            key_to_bytes(key(KeyCode::F(5), KeyModifiers::NONE), false),
+            Some(b"\x1b[15~".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_screen() {
+        let mut parser = vt100::Parser::new(2, 4, 0);
+        parser.process(b"\x1b[31mab\x1b[0mc");
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 2));
+        Screen(parser.screen()).render(buf.area, &mut buf);
+        let mut expected = Buffer::with_lines(vec!["abc ", "    "]);
+        expected.set_style(
+            Rect::new(0, 0, 2, 1),
+            Style::default().fg(Color::Indexed(1)),
+        );
+        expected.set_style(Rect::new(2, 0, 2, 1), Style::default().fg(Color::Reset));
+        expected.set_style(Rect::new(0, 1, 4, 1), Style::default().fg(Color::Reset));
+        expected.set_style(buf.area, Style::default().bg(Color::Reset));
+        assert_eq!(buf, expected);
+    }
+}
@@ -6,7 +6,10 @@ use std::{
 use color_eyre::eyre::Result;
 use crossterm::{
     cursor,
-    event::{Event as CrosstermEvent, KeyEvent, KeyEventKind, MouseEvent},
+    event::{
+        DisableFocusChange, EnableFocusChange, Event as CrosstermEvent, KeyEvent, KeyEventKind,
+        MouseEvent,
+    },
     terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 };
 use futures::{FutureExt, StreamExt};
@@ -153,7 +156,12 @@ impl Tui {
 
     pub fn enter(&mut self) -> Result<()> {
         crossterm::terminal::enable_raw_mode()?;
-        crossterm::execute!(std::io::stderr(), EnterAlternateScreen, cursor::Hide)?;
+        crossterm::execute!(
+            std::io::stderr(),
+            EnterAlternateScreen,
+            EnableFocusChange,
+            cursor::Hide
+        )?;
         self.start();
         Ok(())
     }
@@ -162,7 +170,12 @@ impl Tui {
         self.stop()?;
         if crossterm::terminal::is_raw_mode_enabled()? {
             self.flush()?;
-            crossterm::execute!(std::io::stderr(), LeaveAlternateScreen, cursor::Show)?;
+            crossterm::execute!(
+                std::io::stderr(),
+                DisableFocusChange,
+                LeaveAlternateScreen,
+                cursor::Show
+            )?;
             crossterm::terminal::disable_raw_mode()?;
         }
         Ok(())
@@ -154,6 +154,69 @@ macro_rules! trace_dbg {
     };
 }
 
+/// Formats the duration in a short, human readable form (e.g. `5m` or `2h`).
+pub fn humanize_duration(duration: chrono::Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    match seconds {
+        0..=59 => format!("{seconds}s"),
+        60..=3599 => format!("{}m", seconds / 60),
+        3600..=86399 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86400),
+    }
+}
+
+/// Runs git with the given arguments in the current directory and returns its output.
+async fn git(args: &[&str]) -> Option<String> {
+    let output = tokio::process::Command::new("git")
+        .args(args)
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Returns `true` if the value is an abbreviated or full commit ID.
+///
+/// Commit IDs come from the API, so anything else is rejected before it can
+/// be read as an option by git.
+fn is_commit_id(value: &str) -> bool {
+    (7..=40).contains(&value.len())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, 'a'..='f'))
+}
+
+/// Returns the author of the given commit if it exists in the current repository.
+pub async fn git_commit_author(commit: &str) -> Option<String> {
+    if !is_commit_id(commit) {
+        return None;
+    }
+    let author = git(&["log", "-1", "--format=%an <%ae>", commit]).await?;
+    let author = author.trim();
+    (!author.is_empty()).then(|| author.to_string())
+}
+
+/// Returns the one-line summaries of the commits in `from..to`, newest first.
+pub async fn git_commit_range(from: &str, to: &str) -> Option<Vec<String>> {
+    if !is_commit_id(from) || !is_commit_id(to) {
+        return None;
+    }
+    let range = format!("{from}..{to}");
+    let log = git(&["log", "--format=%h %s", &range]).await?;
+    Some(log.lines().map(String::from).collect())
+}
+
+/// Returns the contents of a file at the given commit.
+pub async fn git_show_file(commit: &str, path: &str) -> Option<String> {
+    if !is_commit_id(commit) {
+        return None;
+    }
+    git(&["show", &format!("{commit}:{path}")]).await
+}
+
 pub fn version() -> String {
     let author = clap::crate_authors!();
 